@@ -0,0 +1,40 @@
+//! Compares the chunked `fill_bytes` implementation against `rand_core`'s generic
+//! `fill_bytes_via_next`.
+//!
+//! Run with `cargo bench --bench fill_bytes`.
+
+use pcg::Pcg;
+use rand_core::{impls, RngCore};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// The size of the buffer that is filled on every iteration
+const BUF_LEN: usize = 1 << 16;
+
+/// The number of times the buffer is filled for each measurement
+const ITERS: u32 = 2_000;
+
+fn measure<F: FnMut(&mut [u8])>(name: &str, mut fill: F) {
+    let mut buf = vec![0u8; BUF_LEN];
+    let start = Instant::now();
+    for _ in 0..ITERS {
+        fill(black_box(&mut buf));
+    }
+    report(name, start.elapsed());
+}
+
+fn report(name: &str, elapsed: Duration) {
+    let bytes = BUF_LEN as f64 * f64::from(ITERS);
+    let throughput = bytes / elapsed.as_secs_f64() / (1 << 30) as f64;
+    println!("{:<20} {:>10.2?} {:>8.2} GiB/s", name, elapsed, throughput);
+}
+
+fn main() {
+    let mut rng = Pcg::default();
+    measure("fill_bytes", |buf| rng.fill_bytes(buf));
+
+    let mut rng = Pcg::default();
+    measure("fill_bytes_via_next", |buf| {
+        impls::fill_bytes_via_next(&mut rng, buf)
+    });
+}
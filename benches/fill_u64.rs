@@ -0,0 +1,39 @@
+//! Compares the lane-parallel `fill_u64_simd` against the scalar `fill_u64`.
+//!
+//! Run with `cargo bench --features simd --bench fill_u64`.
+
+use pcg::Pcg;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// The number of values in the buffer that is filled on every iteration
+const BUF_LEN: usize = 1 << 13;
+
+/// The number of times the buffer is filled for each measurement
+const ITERS: u32 = 2_000;
+
+fn measure<F: FnMut(&mut [u64])>(name: &str, mut fill: F) {
+    let mut buf = vec![0u64; BUF_LEN];
+    let start = Instant::now();
+    for _ in 0..ITERS {
+        fill(black_box(&mut buf));
+    }
+    report(name, start.elapsed());
+}
+
+fn report(name: &str, elapsed: Duration) {
+    let values = BUF_LEN as f64 * f64::from(ITERS);
+    let throughput = values / elapsed.as_secs_f64() / 1e6;
+    println!(
+        "{:<20} {:>10.2?} {:>8.1} M values/s",
+        name, elapsed, throughput
+    );
+}
+
+fn main() {
+    let mut rng = Pcg::default();
+    measure("fill_u64", |buf| rng.fill_u64(buf));
+
+    let mut rng = Pcg::default();
+    measure("fill_u64_simd", |buf| rng.fill_u64_simd(buf));
+}
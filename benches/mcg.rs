@@ -0,0 +1,32 @@
+//! Compares the multiplicative `PcgMcg` against the standard `Pcg`.
+//!
+//! Run with `cargo bench --bench mcg`.
+
+use pcg::{Pcg, PcgMcg};
+use rand_core::RngCore;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// The number of values generated for each measurement
+const VALUES: usize = 1 << 26;
+
+fn measure<R: RngCore>(name: &str, rng: &mut R) {
+    let start = Instant::now();
+    for _ in 0..VALUES {
+        black_box(rng.next_u32());
+    }
+    report(name, start.elapsed());
+}
+
+fn report(name: &str, elapsed: Duration) {
+    let throughput = VALUES as f64 / elapsed.as_secs_f64() / 1e6;
+    println!(
+        "{:<8} {:>10.2?} {:>8.1} M values/s",
+        name, elapsed, throughput
+    );
+}
+
+fn main() {
+    measure("Pcg", &mut Pcg::default());
+    measure("PcgMcg", &mut PcgMcg::default());
+}
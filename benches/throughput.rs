@@ -0,0 +1,43 @@
+//! Measures the throughput of the main ways to generate values.
+//!
+//! Run with `cargo bench --bench throughput`.
+
+use pcg::Pcg;
+use rand_core::RngCore;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// The number of values generated for each measurement
+const VALUES: usize = 1 << 24;
+
+/// The number of values in the buffers that are filled
+const BUF_LEN: usize = 1 << 12;
+
+fn report(name: &str, elapsed: Duration) {
+    let throughput = VALUES as f64 / elapsed.as_secs_f64() / 1e6;
+    println!(
+        "{:<12} {:>10.2?} {:>8.1} M values/s",
+        name, elapsed, throughput
+    );
+}
+
+fn main() {
+    let mut rng = Pcg::default();
+    let start = Instant::now();
+    for _ in 0..VALUES {
+        black_box(rng.next_u64());
+    }
+    report("next_u64", start.elapsed());
+
+    let mut bytes = vec![0u8; BUF_LEN * 8];
+    let start = Instant::now();
+    for _ in 0..VALUES / BUF_LEN {
+        rng.fill_bytes(black_box(&mut bytes));
+    }
+    report("fill_bytes", start.elapsed());
+
+    let mut values = vec![0u64; BUF_LEN];
+    let start = Instant::now();
+    rng.bench_fill(&mut values, VALUES / BUF_LEN);
+    report("fill_u64", start.elapsed());
+}
@@ -0,0 +1,21 @@
+//! Writes an endless stream of output to stdout, for piping into statistical test suites.
+//!
+//! ```sh
+//! cargo run --release --example dump | RNG_test stdin64
+//! ```
+
+use pcg::Pcg;
+use std::io::{self, ErrorKind};
+
+fn main() -> io::Result<()> {
+    let mut rng = Pcg::default();
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    loop {
+        match rng.dump_stream(&mut out, 1 << 20) {
+            // The test suite stops reading once it's done
+            Err(e) if e.kind() == ErrorKind::BrokenPipe => return Ok(()),
+            result => result?,
+        }
+    }
+}
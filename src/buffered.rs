@@ -0,0 +1,200 @@
+//! A wrapper that generates values in blocks and serves them out one at a time.
+
+use crate::{OutputFn, Pcg, XshRr};
+use rand_core::{Error, RngCore};
+
+#[cfg(feature = "std")]
+use std::fmt;
+
+#[cfg(not(feature = "std"))]
+use core::fmt;
+
+/// The number of steps `BufferedPcg` generates at a time
+const BLOCK_LEN: usize = 64;
+
+/// A `Pcg` that pre-generates a block of 64 steps at a time, in the style of `rand_core`'s
+/// `BlockRng`
+///
+/// Each refill runs the generator in a tight loop, and values are then served out of the buffer
+/// until it is exhausted. The output is bit-identical to the wrapped generator's: any mix of
+/// `next_u32`, `next_u64`, and `fill_bytes` calls returns the same values it would have.
+///
+/// # Examples
+///
+/// ```
+/// use pcg::{BufferedPcg, Pcg};
+/// use rand_core::RngCore;
+///
+/// let mut plain = Pcg::new(42, 54);
+/// let mut buffered = BufferedPcg::new(plain.clone());
+/// assert_eq!(buffered.next_u32(), plain.next_u32());
+/// assert_eq!(buffered.next_u64(), plain.next_u64());
+/// ```
+#[derive(Clone)]
+pub struct BufferedPcg<O = XshRr> {
+    rng: Pcg<O>,
+    buf: [u64; BLOCK_LEN],
+    pos: usize,
+}
+
+impl<O: OutputFn> BufferedPcg<O> {
+    /// Wraps a generator, which starts serving values from its current position
+    pub fn new(rng: Pcg<O>) -> BufferedPcg<O> {
+        BufferedPcg {
+            rng,
+            buf: [0; BLOCK_LEN],
+            pos: BLOCK_LEN,
+        }
+    }
+
+    /// Unwraps the generator, rewound past any values that were buffered but not yet served, so
+    /// that it carries on exactly where the wrapper left off
+    pub fn into_inner(self) -> Pcg<O> {
+        let mut rng = self.rng;
+        rng.backstep((BLOCK_LEN - self.pos) as u64);
+        rng
+    }
+
+    /// Returns the output of the next step, refilling the buffer if it has run out
+    fn next_permuted(&mut self) -> u64 {
+        if self.pos == BLOCK_LEN {
+            for value in self.buf.iter_mut() {
+                *value = self.rng.next_permuted();
+            }
+            self.pos = 0;
+        }
+        let value = self.buf[self.pos];
+        self.pos += 1;
+        value
+    }
+}
+
+/// Prints the wrapped generator and how many values are buffered, leaving out the buffered values
+/// themselves
+#[cfg(not(feature = "secure-debug"))]
+impl<O> fmt::Debug for BufferedPcg<O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BufferedPcg")
+            .field("rng", &self.rng)
+            .field("buffered", &(BLOCK_LEN - self.pos))
+            .finish()
+    }
+}
+
+/// Prints `BufferedPcg { .. }` without any field values, so that neither the state nor the
+/// upcoming outputs can leak into logs
+#[cfg(feature = "secure-debug")]
+impl<O> fmt::Debug for BufferedPcg<O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BufferedPcg").finish_non_exhaustive()
+    }
+}
+
+impl<O: OutputFn> RngCore for BufferedPcg<O> {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.record_generated(1);
+        let value = self.next_permuted();
+        if O::BITS == 64 {
+            (value >> 32) as u32
+        } else {
+            value as u32
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.record_generated(1);
+        if O::BITS == 64 {
+            self.next_permuted()
+        } else {
+            let high = self.next_permuted() as u32;
+            let low = self.next_permuted() as u32;
+            (u64::from(high) << 32) | u64::from(low)
+        }
+    }
+
+    /// Fills `dest` with bytes from successive `next_u64` values, in the same little-endian
+    /// layout as `Pcg::fill_bytes`
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let mut chunks = dest.chunks_exact_mut(8);
+        for chunk in &mut chunks {
+            chunk.copy_from_slice(&self.next_u64().to_le_bytes());
+        }
+
+        let tail = chunks.into_remainder();
+        if !tail.is_empty() {
+            let bytes = self.next_u64().to_le_bytes();
+            tail.copy_from_slice(&bytes[..tail.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::RxsMXs;
+
+    #[test]
+    fn test_interleaved_matches_plain() {
+        let mut plain = Pcg::new(42, 54);
+        let mut buffered = BufferedPcg::new(plain.clone());
+
+        // Mixed reads cross several refills, with odd-sized byte fills shifting the alignment
+        for i in 0..200 {
+            match i % 3 {
+                0 => assert_eq!(buffered.next_u32(), plain.next_u32()),
+                1 => assert_eq!(buffered.next_u64(), plain.next_u64()),
+                _ => {
+                    let mut a = [0u8; 13];
+                    let mut b = [0u8; 13];
+                    buffered.fill_bytes(&mut a);
+                    plain.fill_bytes(&mut b);
+                    assert_eq!(a, b);
+                }
+            }
+        }
+        assert_eq!(buffered.into_inner(), plain);
+    }
+
+    #[test]
+    fn test_wide_output_matches_plain() {
+        let mut plain = Pcg::new(42, 54).with_output::<RxsMXs>();
+        let mut buffered = BufferedPcg::new(plain.clone());
+        for _ in 0..150 {
+            assert_eq!(buffered.next_u64(), plain.next_u64());
+            assert_eq!(buffered.next_u32(), plain.next_u32());
+        }
+        assert_eq!(buffered.into_inner(), plain);
+    }
+
+    #[cfg(not(feature = "secure-debug"))]
+    #[test]
+    fn test_debug_omits_buffer() {
+        let mut rng = BufferedPcg::new(Pcg::new(42, 54));
+        rng.next_u32();
+        let upcoming = rng.buf[1];
+        let debug = format!("{:?}", rng);
+        assert!(debug.contains("buffered: 63"), "{}", debug);
+        assert!(!debug.contains(&upcoming.to_string()));
+    }
+
+    #[cfg(feature = "secure-debug")]
+    #[test]
+    fn test_debug_is_redacted() {
+        let mut rng = BufferedPcg::new(Pcg::new(42, 54).with_output::<RxsMXs>());
+        rng.next_u64();
+        let debug = format!("{:?}", rng);
+        assert_eq!(debug, "BufferedPcg { .. }");
+        assert!(!debug.contains(&rng.buf[1].to_string()));
+    }
+
+    #[test]
+    fn test_into_inner_before_first_read() {
+        let rng = Pcg::new(42, 54);
+        assert_eq!(BufferedPcg::new(rng.clone()).into_inner(), rng);
+    }
+}
@@ -0,0 +1,134 @@
+//! A builder for configuring a `Pcg` generator.
+
+use crate::consts::{INIT_SEED, INIT_SEQ};
+use crate::output::{DynPcg, OutputKind};
+use crate::Pcg;
+
+/// A builder for constructing a `Pcg` with a specific seed, stream, and starting position.
+///
+/// Any parameter that isn't set falls back to a default, and a builder with no parameters set
+/// produces the same generator as `Pcg::default()`.
+///
+/// # Examples
+///
+/// ```
+/// use pcg::{Pcg, PcgBuilder};
+///
+/// let rng = PcgBuilder::new().seed(42).stream(54).advance(1000).build();
+///
+/// let mut expected = Pcg::new(42, 54);
+/// expected.advance(1000);
+/// assert_eq!(rng, expected);
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct PcgBuilder {
+    seed: u64,
+    stream: u64,
+    advance: u64,
+}
+
+impl PcgBuilder {
+    /// Creates a builder with the default seed and stream, and no advancement
+    pub fn new() -> PcgBuilder {
+        PcgBuilder {
+            seed: INIT_SEED,
+            stream: INIT_SEQ,
+            advance: 0,
+        }
+    }
+
+    /// Sets the seed, which supplies the initial state of the generator
+    pub fn seed(mut self, seed: u64) -> PcgBuilder {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets the stream ID (the `seq` param of `Pcg::new`)
+    pub fn stream(mut self, stream: u64) -> PcgBuilder {
+        self.stream = stream;
+        self
+    }
+
+    /// Sets the number of steps to advance the generator by after seeding it
+    pub fn advance(mut self, delta: u64) -> PcgBuilder {
+        self.advance = delta;
+        self
+    }
+
+    /// Constructs the configured generator
+    pub fn build(&self) -> Pcg {
+        let mut pcg = Pcg::new(self.seed, self.stream);
+        pcg.advance(self.advance);
+        pcg
+    }
+
+    /// Constructs the configured generator with the output permutation selected by `kind`.
+    ///
+    /// The permutation only changes how each state is turned into an output, so every kind walks
+    /// through the same sequence of states.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::output::OutputKind;
+    /// use pcg::PcgBuilder;
+    /// use rand_core::RngCore;
+    ///
+    /// let builder = PcgBuilder::new().seed(42).stream(54);
+    /// let mut fast = builder.output(OutputKind::XshRs);
+    /// let mut wide = builder.output(OutputKind::RxsMXs);
+    /// assert_ne!(fast.next_u64(), wide.next_u64());
+    /// ```
+    pub fn output(&self, kind: OutputKind) -> DynPcg {
+        DynPcg::new(self.build(), kind)
+    }
+}
+
+impl Default for PcgBuilder {
+    fn default() -> Self {
+        PcgBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::RngCore;
+
+    #[test]
+    fn test_default_matches_pcg_default() {
+        let mut built = PcgBuilder::new().build();
+        let mut default = Pcg::default();
+        assert_eq!(built, default);
+
+        for _ in 0..100 {
+            assert_eq!(built.next_u32(), default.next_u32());
+        }
+    }
+
+    #[test]
+    fn test_output_kinds_are_distinct() {
+        let builder = PcgBuilder::new().seed(42).stream(54);
+        let mut rngs = [OutputKind::XshRr, OutputKind::XshRs, OutputKind::RxsMXs]
+            .map(|kind| builder.output(kind));
+        let outputs = rngs
+            .each_mut()
+            .map(|rng| [rng.next_u64(), rng.next_u64(), rng.next_u64()]);
+        assert_ne!(outputs[0], outputs[1]);
+        assert_ne!(outputs[1], outputs[2]);
+        assert_ne!(outputs[0], outputs[2]);
+
+        let mut default = builder.output(OutputKind::default());
+        assert_eq!(default.kind(), OutputKind::XshRr);
+        let mut expected = builder.build();
+        assert_eq!(default.next_u64(), expected.next_u64());
+    }
+
+    #[test]
+    fn test_seed_and_stream() {
+        assert_eq!(
+            PcgBuilder::new().seed(42).stream(54).build(),
+            Pcg::new(42, 54)
+        );
+    }
+}
@@ -9,3 +9,46 @@ pub const INIT_INC: u64 = 0xda3e_39cb_94b9_5bdb;
 /// The value to multiply the state with when a random number is generated in order to
 /// alter the random number generator's state
 pub const INCREMENTOR: u64 = 6_364_136_223_846_793_005;
+
+/// The initial/default state to initialize the Pcg64 struct with
+pub const INIT_STATE_128: u128 = 0x979c_9a98_d846_2005_7d3e_9cb6_cfe0_549b;
+
+/// The initial/default incrementing value to initialize the Pcg64 struct with
+pub const INIT_INC_128: u128 = 0x0000_0000_0000_0001_da3e_39cb_94b9_5bdb;
+
+/// The value to multiply the 128-bit state with when a random number is generated by `Pcg64`
+pub const INCREMENTOR_128: u128 = 0x2360_ed05_1fc6_5da4_4385_df64_9fcc_f645;
+
+/// The seed that, passed to `Pcg::new` along with `INIT_SEQ`, yields the default state
+pub const INIT_SEED: u64 = 0xcafe_f00d_d15e_a5e5;
+
+/// The sequence that, passed to `Pcg::new` along with `INIT_SEED`, yields the default increment
+pub const INIT_SEQ: u64 = INIT_INC >> 1;
+
+/// The multiplier used by the RXS M XS output permutation to scramble the shifted state
+pub const RXS_M_XS_MULTIPLIER: u64 = 12_605_985_483_714_917_081;
+
+/// The multiplicative inverse of `RXS_M_XS_MULTIPLIER` modulo `2^64`, used to invert the RXS M XS
+/// permutation
+pub const RXS_M_XS_INVERSE: u64 = 0xd04c_a582_acb8_6d69;
+
+/// The increment of the SplitMix64 generator, `2^64` divided by the golden ratio
+pub const GOLDEN_GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// The first multiplier of the SplitMix64 finalizer
+pub const MIX_MULTIPLIER_1: u64 = 0xbf58_476d_1ce4_e5b9;
+
+/// The second multiplier of the SplitMix64 finalizer
+pub const MIX_MULTIPLIER_2: u64 = 0x94d0_49bb_1331_11eb;
+
+/// The multiplicative inverse of `INCREMENTOR` modulo `2^64`, used to undo a step of the LCG
+pub const INCREMENTOR_INVERSE: u64 = 0xc097_ef87_329e_28a5;
+
+/// The offset basis of the 64-bit FNV-1a hash
+pub const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// The prime of the 64-bit FNV-1a hash
+pub const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// The fixed increment of the reference "oneseq" generators, `PCG_DEFAULT_INCREMENT_64`
+pub const ONESEQ_INC: u64 = 1_442_695_040_888_963_407;
@@ -0,0 +1,52 @@
+//! Logging with `defmt`, enabled with the `defmt` feature.
+//!
+//! `Pcg` and `PcgSeed` implement `defmt::Format` with the same fields as their `Debug` impls, so
+//! embedded targets can log them without `core::fmt`. The state and increment are printed as
+//! hex. With the `secure-debug` feature, `Pcg` is printed as `Pcg { .. }`, just like its `Debug`
+//! output. This doesn't need the `std` feature.
+
+use crate::{Pcg, PcgSeed};
+use defmt::{Format, Formatter};
+
+#[cfg(not(feature = "secure-debug"))]
+impl<O> Format for Pcg<O> {
+    fn format(&self, fmt: Formatter) {
+        defmt::write!(
+            fmt,
+            "Pcg {{ state: {=u64:#x}, inc: {=u64:#x} }}",
+            self.state,
+            self.inc
+        )
+    }
+}
+
+/// Prints `Pcg { .. }` without any field values, so that the state can't leak into logs
+#[cfg(feature = "secure-debug")]
+impl<O> Format for Pcg<O> {
+    fn format(&self, fmt: Formatter) {
+        defmt::write!(fmt, "Pcg {{ .. }}")
+    }
+}
+
+impl<const N: usize> Format for PcgSeed<N> {
+    fn format(&self, fmt: Formatter) {
+        defmt::write!(fmt, "PcgSeed({=[u8]})", &self.0[..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::RxsMXs;
+
+    #[test]
+    fn test_implements_format() {
+        // Formatting needs a global logger, which only embedded binaries provide, so this only
+        // checks that the impls exist
+        fn assert_format<T: Format>() {}
+        assert_format::<Pcg>();
+        assert_format::<Pcg<RxsMXs>>();
+        assert_format::<PcgSeed>();
+        assert_format::<crate::Pcg64Seed>();
+    }
+}
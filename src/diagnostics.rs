@@ -0,0 +1,126 @@
+//! A quick statistical self-test for spotting degenerate generators.
+
+use crate::{OutputFn, Pcg};
+use rand_core::RngCore;
+
+/// The number of possible byte values, which is the number of buckets in the chi-square test
+const BUCKETS: usize = 256;
+
+/// The smallest number of samples `self_test` accepts, which puts at least 5 samples in each
+/// bucket on average, the usual rule of thumb for a chi-square test
+const MIN_SAMPLES: usize = 5 * BUCKETS;
+
+/// The chi-square statistic at or above which the test fails.
+///
+/// With 255 degrees of freedom the statistic has a mean of 255 and a standard deviation of about
+/// 22.6, so a healthy generator only exceeds this threshold with a probability below `10^-8`.
+const THRESHOLD: f64 = 400.0;
+
+impl<O: OutputFn> Pcg<O> {
+    /// Runs a quick chi-square test of whether the generator's bytes look uniformly distributed.
+    ///
+    /// `samples` bytes are drawn with `fill_bytes` and counted by value, and the test passes
+    /// unless the counts stray far from uniform. The threshold is lenient, so a working generator
+    /// is all but certain to pass.
+    ///
+    /// This is a diagnostic for catching generators that have been broken outright, for example by
+    /// custom seeding or state manipulation gone wrong. It is not a proof of quality: plenty of
+    /// poor generators produce uniform bytes, and a passing result says nothing about
+    /// correlations between values. Use a dedicated suite like PractRand or TestU01 for that.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `samples` is less than 1280, which is too few for the test to be meaningful.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut rng = Pcg::default();
+    /// assert!(rng.self_test(100_000));
+    /// ```
+    pub fn self_test(&mut self, samples: usize) -> bool {
+        chi_square_bytes(self, samples) < THRESHOLD
+    }
+}
+
+/// Computes the chi-square statistic of `samples` bytes from `rng`, bucketed by value
+fn chi_square_bytes<R: RngCore>(rng: &mut R, samples: usize) -> f64 {
+    assert!(
+        samples >= MIN_SAMPLES,
+        "the self test needs at least {} samples, got {}",
+        MIN_SAMPLES,
+        samples
+    );
+
+    let mut counts = [0u64; BUCKETS];
+    let mut buf = [0u8; 64];
+    let mut remaining = samples;
+    while remaining > 0 {
+        let len = remaining.min(buf.len());
+        rng.fill_bytes(&mut buf[..len]);
+        for &byte in buf[..len].iter() {
+            counts[byte as usize] += 1;
+        }
+        remaining -= len;
+    }
+
+    let expected = samples as f64 / BUCKETS as f64;
+    counts
+        .iter()
+        .map(|&count| {
+            let diff = count as f64 - expected;
+            diff * diff / expected
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::{RxsMXs, XshRs};
+    use rand_core::{impls, Error};
+
+    /// A broken generator that always produces the same value
+    struct Constant(u64);
+
+    impl RngCore for Constant {
+        fn next_u32(&mut self) -> u32 {
+            self.0 as u32
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.0
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            impls::fill_bytes_via_next(self, dest)
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_default_passes() {
+        assert!(Pcg::default().self_test(100_000));
+        assert!(Pcg::new(42, 54).self_test(MIN_SAMPLES));
+        assert!(Pcg::new(42, 54).with_output::<XshRs>().self_test(100_000));
+        assert!(Pcg::new(42, 54).with_output::<RxsMXs>().self_test(100_000));
+    }
+
+    #[test]
+    fn test_constant_output_fails() {
+        assert!(chi_square_bytes(&mut Constant(0), 100_000) >= THRESHOLD);
+        assert!(chi_square_bytes(&mut Constant(0x0123_4567_89ab_cdef), 100_000) >= THRESHOLD);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rejects_too_few_samples() {
+        Pcg::default().self_test(MIN_SAMPLES - 1);
+    }
+}
@@ -0,0 +1,274 @@
+//! Distributions with precomputed parameters, for drawing many samples cheaply.
+
+use crate::{OutputFn, Pcg};
+use rand_core::RngCore;
+
+#[cfg(feature = "std")]
+use std::fmt;
+
+#[cfg(not(feature = "std"))]
+use core::fmt;
+
+/// `2^64`, the number of possible `u64` values
+const U64_RANGE: f64 = 18_446_744_073_709_551_616.0;
+
+/// A Bernoulli distribution, which is `true` with probability `p`
+///
+/// The probability is converted into an integer threshold when the distribution is created, so
+/// every sample is a single `next_u64` compared against the threshold without any floating point
+/// math. The probability is rounded to a multiple of `2^-64`.
+///
+/// # Examples
+///
+/// ```
+/// use pcg::{Bernoulli, Pcg};
+///
+/// let coin = Bernoulli::new(0.3).unwrap();
+/// let mut rng = Pcg::default();
+/// let heads = (0..100).filter(|_| coin.sample(&mut rng)).count();
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Bernoulli {
+    /// Samples below this are `true`, except that `u64::MAX` means every sample is `true`
+    threshold: u64,
+}
+
+impl Bernoulli {
+    /// Creates a distribution that is `true` with probability `p`.
+    ///
+    /// Returns an error if `p` is outside of `[0, 1]` or is NaN.
+    pub fn new(p: f64) -> Result<Bernoulli, BernoulliError> {
+        if !(0.0..=1.0).contains(&p) {
+            return Err(BernoulliError);
+        }
+        // Only `p == 1` maps to the always-true `u64::MAX`. Below 1, `p * 2^64` is at most
+        // `2^64 - 2^11`, so the threshold always leaves some samples `false`
+        let threshold = if p == 1.0 {
+            u64::MAX
+        } else {
+            (p * U64_RANGE) as u64
+        };
+        Ok(Bernoulli { threshold })
+    }
+
+    /// Draws a sample, which consumes a single `next_u64` from `rng`.
+    pub fn sample<O: OutputFn>(&self, rng: &mut Pcg<O>) -> bool {
+        let value = rng.next_u64();
+        self.threshold == u64::MAX || value < self.threshold
+    }
+}
+
+/// The error returned when creating a `Bernoulli` distribution with an invalid probability
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct BernoulliError;
+
+impl fmt::Display for BernoulliError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the probability must be in the range [0, 1]")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BernoulliError {}
+
+/// A table for sampling from a fixed discrete distribution with the alias method
+///
+/// Building the table from `n` weights takes `O(n)` time, and afterwards every sample takes
+/// constant time, regardless of how many categories there are: one `next_bounded_u64` to pick a
+/// column and one `next_f64` to pick between the column's category and its alias. This requires
+/// the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// use pcg::{AliasTable, Pcg};
+///
+/// let table = AliasTable::new(&[1.0, 2.0, 7.0]).unwrap();
+/// let mut rng = Pcg::default();
+/// let category = table.sample(&mut rng);
+/// assert!(category < 3);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AliasTable {
+    /// The probability of keeping each column's own category rather than its alias
+    prob: Vec<f64>,
+    /// The category each column falls back to
+    alias: Vec<usize>,
+}
+
+#[cfg(feature = "std")]
+impl AliasTable {
+    /// Builds a table from a slice of weights, which don't need to sum to 1.
+    ///
+    /// Categories with a weight of zero are never sampled. Returns an error if there are no
+    /// weights, any weight is negative, infinite or NaN, or all of the weights are zero.
+    pub fn new(weights: &[f64]) -> Result<AliasTable, AliasTableError> {
+        if weights.is_empty() {
+            return Err(AliasTableError::Empty);
+        }
+        if weights.iter().any(|&w| !(w >= 0.0 && w.is_finite())) {
+            return Err(AliasTableError::InvalidWeight);
+        }
+        let total: f64 = weights.iter().sum();
+        if total == 0.0 {
+            return Err(AliasTableError::ZeroTotal);
+        }
+
+        // Scale the weights so that their average is 1, then pair every column that is short of
+        // 1 with one that has a surplus
+        let n = weights.len();
+        let mut prob: Vec<f64> = weights.iter().map(|&w| w * n as f64 / total).collect();
+        let mut alias: Vec<usize> = (0..n).collect();
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) = (0..n).partition(|&i| prob[i] < 1.0);
+
+        while let (Some(&s), Some(&l)) = (small.last(), large.last()) {
+            small.pop();
+            alias[s] = l;
+            prob[l] -= 1.0 - prob[s];
+            if prob[l] < 1.0 {
+                large.pop();
+                small.push(l);
+            }
+        }
+        // Whatever is left over is only short of, or over, 1 because of rounding
+        for i in small.into_iter().chain(large) {
+            prob[i] = 1.0;
+        }
+        Ok(AliasTable { prob, alias })
+    }
+
+    /// Returns the number of categories in the table
+    pub fn len(&self) -> usize {
+        self.prob.len()
+    }
+
+    /// Returns whether the table has no categories, which is never the case
+    pub fn is_empty(&self) -> bool {
+        self.prob.is_empty()
+    }
+
+    /// Draws a category index in `0..self.len()`
+    pub fn sample<O: OutputFn>(&self, rng: &mut Pcg<O>) -> usize {
+        let column = rng.next_bounded_u64(self.prob.len() as u64) as usize;
+        if rng.next_f64() < self.prob[column] {
+            column
+        } else {
+            self.alias[column]
+        }
+    }
+}
+
+/// An error that can occur when building an `AliasTable`
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum AliasTableError {
+    /// There were no weights
+    Empty,
+    /// A weight was negative, infinite or NaN
+    InvalidWeight,
+    /// All of the weights were zero
+    ZeroTotal,
+}
+
+impl fmt::Display for AliasTableError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AliasTableError::Empty => write!(f, "there must be at least one weight"),
+            AliasTableError::InvalidWeight => {
+                write!(f, "weights must be finite and non-negative")
+            }
+            AliasTableError::ZeroTotal => write!(f, "at least one weight must be positive"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AliasTableError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bernoulli_rate() {
+        let mut rng = Pcg::new(42, 54);
+        let samples = 100_000;
+        for &p in [0.0, 0.1, 0.5, 0.9, 1.0].iter() {
+            let dist = Bernoulli::new(p).unwrap();
+            let hits = (0..samples).filter(|_| dist.sample(&mut rng)).count();
+            let rate = hits as f64 / samples as f64;
+            assert!((rate - p).abs() < 0.01, "rate was {} for p = {}", rate, p);
+        }
+    }
+
+    #[test]
+    fn test_bernoulli_extremes() {
+        let mut rng = Pcg::new(42, 54);
+        let never = Bernoulli::new(0.0).unwrap();
+        let always = Bernoulli::new(1.0).unwrap();
+        for _ in 0..1000 {
+            assert!(!never.sample(&mut rng));
+            assert!(always.sample(&mut rng));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_alias_table_frequencies() {
+        let weights = [5.0, 0.0, 1.0, 3.0, 1.0];
+        let table = AliasTable::new(&weights).unwrap();
+        let mut rng = Pcg::new(42, 54);
+        let samples = 100_000;
+        let mut counts = [0usize; 5];
+        for _ in 0..samples {
+            counts[table.sample(&mut rng)] += 1;
+        }
+
+        assert_eq!(counts[1], 0);
+        for (&count, &weight) in counts.iter().zip(weights.iter()) {
+            let rate = count as f64 / samples as f64;
+            assert!(
+                (rate - weight / 10.0).abs() < 0.01,
+                "rate was {} for weight {}",
+                rate,
+                weight
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_alias_table_single_category() {
+        let table = AliasTable::new(&[0.25]).unwrap();
+        let mut rng = Pcg::new(42, 54);
+        assert_eq!(table.len(), 1);
+        for _ in 0..100 {
+            assert_eq!(table.sample(&mut rng), 0);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_alias_table_rejects_invalid_weights() {
+        assert_eq!(AliasTable::new(&[]), Err(AliasTableError::Empty));
+        assert_eq!(
+            AliasTable::new(&[1.0, -1.0]),
+            Err(AliasTableError::InvalidWeight)
+        );
+        assert_eq!(
+            AliasTable::new(&[1.0, f64::NAN]),
+            Err(AliasTableError::InvalidWeight)
+        );
+        assert_eq!(
+            AliasTable::new(&[0.0, 0.0]),
+            Err(AliasTableError::ZeroTotal)
+        );
+    }
+
+    #[test]
+    fn test_bernoulli_rejects_invalid_probabilities() {
+        for &p in [-0.1, 1.1, f64::NAN, f64::INFINITY].iter() {
+            assert_eq!(Bernoulli::new(p), Err(BernoulliError));
+        }
+    }
+}
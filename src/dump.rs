@@ -0,0 +1,59 @@
+//! Streaming raw output to a writer, for use with statistical test suites.
+
+use crate::{OutputFn, Pcg};
+use rand_core::RngCore;
+use std::io::{self, Write};
+
+/// The size of the buffer that output is generated into before it's written
+const CHUNK_LEN: usize = 8192;
+
+impl<O: OutputFn> Pcg<O> {
+    /// Writes `bytes` bytes of output to `writer`.
+    ///
+    /// The bytes are the same ones `fill_bytes` would produce for a buffer of the same length,
+    /// so each 64-bit value is written in little-endian order. Output is generated in chunks of
+    /// a few kilobytes, so there's no need to wrap `writer` in a `BufWriter`. This makes it easy
+    /// to feed the generator to test suites like PractRand or TestU01.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut rng = Pcg::default();
+    /// let mut out = Vec::new();
+    /// rng.dump_stream(&mut out, 100).unwrap();
+    /// assert_eq!(out.len(), 100);
+    /// ```
+    pub fn dump_stream<W: Write>(&mut self, writer: &mut W, bytes: u64) -> io::Result<()> {
+        let mut buf = [0u8; CHUNK_LEN];
+        let mut remaining = bytes;
+        while remaining > 0 {
+            let len = remaining.min(CHUNK_LEN as u64) as usize;
+            self.fill_bytes(&mut buf[..len]);
+            writer.write_all(&buf[..len])?;
+            remaining -= len as u64;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dump_stream_matches_fill_bytes() {
+        for &len in [0, 1, 7, 8, CHUNK_LEN - 1, CHUNK_LEN, 3 * CHUNK_LEN + 5].iter() {
+            let mut a = Pcg::new(42, 54);
+            let mut b = a.clone();
+            let mut dumped = Vec::new();
+            a.dump_stream(&mut dumped, len as u64).unwrap();
+
+            let mut filled = vec![0u8; len];
+            b.fill_bytes(&mut filled);
+            assert_eq!(dumped, filled);
+            assert_eq!(a, b);
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! A trait that unifies the generators of different widths.
+
+use crate::output::{RxsMXs, XshRr, XshRs};
+use crate::{Pcg, Pcg64};
+use rand_core::RngCore;
+
+/// A PCG generator that natively produces values of type `Output`
+///
+/// This allows generic code to work with generators of different widths, using each one's
+/// natural output size.
+///
+/// # Examples
+///
+/// ```
+/// use pcg::{Pcg32, Pcg64, PcgEngine};
+///
+/// fn first_outputs<E: PcgEngine>(rng: &mut E) -> [E::Output; 2] {
+///     [rng.next_output(), rng.next_output()]
+/// }
+///
+/// let small: [u32; 2] = first_outputs(&mut Pcg32::new(42, 54));
+/// let large: [u64; 2] = first_outputs(&mut Pcg64::new(42, 54));
+/// ```
+pub trait PcgEngine: RngCore {
+    /// The type of the values the generator produces in a single step
+    type Output;
+
+    /// Generates a single value of the generator's native width
+    fn next_output(&mut self) -> Self::Output;
+}
+
+impl PcgEngine for Pcg<XshRr> {
+    type Output = u32;
+
+    fn next_output(&mut self) -> u32 {
+        self.next_u32()
+    }
+}
+
+impl PcgEngine for Pcg<XshRs> {
+    type Output = u32;
+
+    fn next_output(&mut self) -> u32 {
+        self.next_u32()
+    }
+}
+
+impl PcgEngine for Pcg<RxsMXs> {
+    type Output = u64;
+
+    fn next_output(&mut self) -> u64 {
+        self.next_u64()
+    }
+}
+
+impl PcgEngine for Pcg64 {
+    type Output = u64;
+
+    fn next_output(&mut self) -> u64 {
+        self.next_u64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_output_matches_native_width() {
+        let mut a = Pcg::new(42, 54);
+        let mut b = a.clone();
+        assert_eq!(a.next_output(), b.next_u32());
+
+        let mut a = Pcg::new(42, 54).with_output::<RxsMXs>();
+        let mut b = a.clone();
+        assert_eq!(a.next_output(), b.next_u64());
+
+        let mut a = Pcg64::new(42, 54);
+        let mut b = a.clone();
+        assert_eq!(a.next_output(), b.next_u64());
+    }
+}
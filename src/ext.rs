@@ -0,0 +1,282 @@
+//! An extended PCG generator with a table of extension words.
+
+use crate::consts::{INCREMENTOR, ONESEQ_INC};
+use crate::output::{inverse_rxs_m_xs, rxs_m_xs, RxsMXs};
+use crate::Pcg;
+use rand_core::{impls, Error, RngCore};
+
+#[cfg(feature = "std")]
+use std::fmt;
+
+#[cfg(not(feature = "std"))]
+use core::fmt;
+
+/// The base-2 logarithm of the number of steps between advances of the extension table
+const TICK_BITS: u32 = 16;
+
+/// The low bits of the base state that are all zero on steps where the extension table advances
+const TICK_MASK: u64 = (1 << TICK_BITS) - 1;
+
+/// The `PcgExt` struct is an extended PCG generator, following the reference library's
+/// `extended` generators.
+///
+/// It pairs a base generator, which uses the RXS M XS permutation to produce 64-bit values, with a
+/// table of `K` extension words. Each output of the base generator is XORed with the table entry
+/// picked by the low bits of the base state. Whenever the low 16 bits of the base state are all
+/// zero, which happens once every `2^16` steps, the table advances. Each word is an RXS M XS
+/// output, so it is mapped back to its state, stepped through an LCG with its own increment, and
+/// permuted again, carrying into the next word when it wraps around to zero. This extends the
+/// period far beyond the `2^64` of the base generator.
+///
+/// The construction matches the reference `extended<log2(K), 16, setseq_rxs_m_xs_64_64,
+/// oneseq_rxs_m_xs_64_64, true>`, including how the table is filled when seeding.
+///
+/// `K` must be a power of two, which is checked at compile time:
+///
+/// ```compile_fail
+/// use pcg::PcgExt;
+///
+/// let rng = PcgExt::<3>::new(42, 54);
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct PcgExt<const K: usize> {
+    base: Pcg<RxsMXs>,
+    extension: [u64; K],
+}
+
+impl<const K: usize> PcgExt<K> {
+    /// Fails to compile for table sizes that aren't a power of two
+    const VALID_SIZE: () = assert!(
+        K.is_power_of_two(),
+        "the extension table size must be a power of two"
+    );
+
+    /// Constructs a new extended generator with a particular seed and sequence.
+    ///
+    /// The base generator is constructed with `Pcg::new`, and then fills the extension table
+    /// itself, like the reference generators do when they aren't given a table: two values are
+    /// drawn, and their difference is XORed into each of the next `K` values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::PcgExt;
+    /// use rand_core::RngCore;
+    ///
+    /// let mut rng = PcgExt::<16>::new(42, 54);
+    /// let value = rng.next_u64();
+    /// ```
+    pub fn new(seed: u64, seq: u64) -> PcgExt<K> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_SIZE;
+        let mut base: Pcg<RxsMXs> = Pcg::new(seed, seq).with_output();
+        let lhs = base.next_u64();
+        let rhs = base.next_u64();
+        let xdiff = lhs.wrapping_sub(rhs);
+        let mut extension = [0u64; K];
+        for word in extension.iter_mut() {
+            *word = base.next_u64() ^ xdiff;
+        }
+        PcgExt { base, extension }
+    }
+
+    /// Returns the table of extension words
+    pub fn extension(&self) -> &[u64; K] {
+        &self.extension
+    }
+
+    /// Advances every word of the extension table, carrying into the next word whenever a word
+    /// wraps around to zero
+    fn advance_table(&mut self) {
+        let mut carry = false;
+        for (i, word) in self.extension.iter_mut().enumerate() {
+            if carry {
+                carry = step_word(word, i + 1);
+            }
+            let wrapped = step_word(word, i + 1);
+            carry = carry || wrapped;
+        }
+    }
+}
+
+/// Steps an extension word, returning whether it wrapped around to zero
+///
+/// The word is an RXS M XS output, so it is inverted back to its state, which is stepped through
+/// the reference "oneseq" LCG with `2 * i` added to the increment, and permuted again.
+fn step_word(word: &mut u64, i: usize) -> bool {
+    let state = inverse_rxs_m_xs(*word)
+        .wrapping_mul(INCREMENTOR)
+        .wrapping_add(ONESEQ_INC)
+        .wrapping_add(2 * i as u64);
+    *word = rxs_m_xs(state);
+    *word == 0
+}
+
+#[cfg(not(feature = "secure-debug"))]
+impl<const K: usize> fmt::Debug for PcgExt<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PcgExt")
+            .field("base", &self.base)
+            .field("extension", &self.extension)
+            .finish()
+    }
+}
+
+/// Prints `PcgExt { .. }` without any field values, so that the state can't leak into logs
+#[cfg(feature = "secure-debug")]
+impl<const K: usize> fmt::Debug for PcgExt<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PcgExt").finish_non_exhaustive()
+    }
+}
+
+impl<const K: usize> RngCore for PcgExt<K> {
+    /// Generates a 32-bit value from the high 32 bits of `next_u64`
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let state = self.base.state();
+        if state & TICK_MASK == 0 {
+            self.advance_table();
+        }
+        let index = (state as usize) & (K - 1);
+        self.base.next_u64() ^ self.extension[index]
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_answer() {
+        // Produced by pcg-cpp's `pcg_engines::ext_std64<2, 16, setseq_rxs_m_xs_64_64>`, the same
+        // `extended` template as `pcg32_k16` with 64-bit words, seeded with `(42, 54)`. The table
+        // is what its `operator<<` prints after construction.
+        let mut rng = PcgExt::<4>::new(42, 54);
+        assert_eq!(
+            *rng.extension(),
+            [
+                0xbb1c_f4ed_48e7_4041,
+                0xe2c7_9c55_b787_98cb,
+                0xa231_3cb8_e471_0999,
+                0x5a64_c654_cc89_9c12,
+            ]
+        );
+        let expected = [
+            0x5cfe_211d_c278_9e2b,
+            0xfe71_3a62_5609_bea5,
+            0x9f7b_a20b_6fa3_47a4,
+            0x4367_4c24_0763_379c,
+        ];
+        for &value in expected.iter() {
+            assert_eq!(rng.next_u64(), value);
+        }
+    }
+
+    #[test]
+    fn test_table_advance_known_answer() {
+        // From the same pcg-cpp generator as `test_known_answer`, whose first table advance comes
+        // after 33778 outputs
+        let mut rng = PcgExt::<4>::new(42, 54);
+        for _ in 0..33778 {
+            rng.next_u64();
+        }
+        assert_eq!(rng.base.state() & TICK_MASK, 0);
+        rng.next_u64();
+        assert_eq!(
+            *rng.extension(),
+            [
+                0xf603_00f6_f619_1321,
+                0x5ba1_26f8_4c93_38da,
+                0xf580_772a_0453_dea3,
+                0x2be7_e176_fd3b_d5b9,
+            ]
+        );
+        assert_eq!(rng.next_u64(), 0x3b86_cca3_7e86_2442);
+    }
+
+    #[test]
+    fn test_known_answer_k16() {
+        // Produced by pcg-cpp's `pcg_engines::ext_std64<4, 16, setseq_rxs_m_xs_64_64>` seeded with
+        // `(42, 54)`, after discarding 100000 outputs so that the table has advanced
+        let mut rng = PcgExt::<16>::new(42, 54);
+        for _ in 0..100_000 {
+            rng.next_u64();
+        }
+        let expected = [
+            0xae9b_303c_cad1_787c,
+            0xe35c_34bb_692d_175b,
+            0xc655_5e85_ea0a_7a0c,
+            0x246b_4486_e6fd_44f2,
+        ];
+        for &value in expected.iter() {
+            assert_eq!(rng.next_u64(), value);
+        }
+    }
+
+    #[test]
+    fn test_bit_balance() {
+        let mut rng = PcgExt::<16>::new(42, 54);
+        let samples = 10_000;
+        let mut counts = [0usize; 64];
+
+        for _ in 0..samples {
+            let value = rng.next_u64();
+            for (bit, count) in counts.iter_mut().enumerate() {
+                *count += ((value >> bit) & 1) as usize;
+            }
+        }
+
+        for (bit, &count) in counts.iter().enumerate() {
+            let ratio = count as f64 / samples as f64;
+            assert!(
+                (0.45..0.55).contains(&ratio),
+                "bit {} was set in {} of samples",
+                bit,
+                ratio
+            );
+        }
+    }
+
+    #[test]
+    fn test_differs_from_base() {
+        let mut ext = PcgExt::<4>::new(42, 54);
+        let mut base = Pcg::new(42, 54).with_output::<RxsMXs>();
+        let matching = (0..1000)
+            .filter(|_| ext.next_u64() == base.next_u64())
+            .count();
+        assert_eq!(matching, 0);
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let mut a = PcgExt::<8>::new(1, 2);
+        let mut b = PcgExt::<8>::new(1, 2);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert_ne!(PcgExt::<8>::new(1, 2), PcgExt::<8>::new(1, 3));
+    }
+
+    #[test]
+    fn test_table_advances() {
+        let mut rng = PcgExt::<2>::new(42, 54);
+        let initial = *rng.extension();
+        for _ in 0..=TICK_MASK {
+            rng.next_u64();
+        }
+        assert_ne!(*rng.extension(), initial);
+    }
+}
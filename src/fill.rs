@@ -0,0 +1,295 @@
+//! Methods for filling whole slices with random values at once.
+
+use crate::{OutputFn, Pcg};
+use rand_core::RngCore;
+
+#[cfg(feature = "std")]
+use std::hint::black_box;
+
+#[cfg(not(feature = "std"))]
+use core::hint::black_box;
+
+/// Integer types that `Pcg::fill` can fill a slice of.
+///
+/// This trait is sealed, and is implemented for `u8`, `u16`, `u32`, `u64`, and `u128`.
+pub trait Fillable: sealed::Sealed {}
+
+mod sealed {
+    use crate::{OutputFn, Pcg};
+
+    pub trait Sealed: Sized {
+        /// Fills `dest` with values from `rng`
+        fn fill_from<O: OutputFn>(rng: &mut Pcg<O>, dest: &mut [Self]);
+    }
+}
+
+impl Fillable for u8 {}
+impl Fillable for u16 {}
+impl Fillable for u32 {}
+impl Fillable for u64 {}
+impl Fillable for u128 {}
+
+impl sealed::Sealed for u8 {
+    fn fill_from<O: OutputFn>(rng: &mut Pcg<O>, dest: &mut [u8]) {
+        rng.fill_bytes(dest);
+    }
+}
+
+impl sealed::Sealed for u16 {
+    fn fill_from<O: OutputFn>(rng: &mut Pcg<O>, dest: &mut [u16]) {
+        // Each `next_u64` is split into four values, lowest bits first, like `fill_bytes`
+        for chunk in dest.chunks_mut(4) {
+            let mut value = rng.next_u64();
+            for word in chunk.iter_mut() {
+                *word = value as u16;
+                value >>= 16;
+            }
+        }
+    }
+}
+
+impl sealed::Sealed for u32 {
+    fn fill_from<O: OutputFn>(rng: &mut Pcg<O>, dest: &mut [u32]) {
+        rng.fill_u32(dest);
+    }
+}
+
+impl sealed::Sealed for u64 {
+    fn fill_from<O: OutputFn>(rng: &mut Pcg<O>, dest: &mut [u64]) {
+        rng.fill_u64(dest);
+    }
+}
+
+impl sealed::Sealed for u128 {
+    fn fill_from<O: OutputFn>(rng: &mut Pcg<O>, dest: &mut [u128]) {
+        for value in dest.iter_mut() {
+            *value = rng.next_u128();
+        }
+    }
+}
+
+impl<O: OutputFn> Pcg<O> {
+    /// Fills a slice of any integer type that implements `Fillable`.
+    ///
+    /// Values are generated from `next_u64`, with no bits thrown away:
+    ///
+    /// * `u8` slices are filled with `fill_bytes`.
+    /// * `u16` slices take four values from each `next_u64`, starting from its lowest bits, so
+    ///   the values match a `fill_bytes` of the same length read as little-endian `u16`s.
+    /// * `u32` and `u64` slices are filled with `fill_u32` and `fill_u64`.
+    /// * Each `u128` is built from two `next_u64` calls like `next_u128`, with the first call
+    ///   providing the high 64 bits. This doesn't depend on the platform's byte order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut rng = Pcg::default();
+    /// let mut shorts = [0u16; 10];
+    /// let mut wide = [0u128; 3];
+    /// rng.fill(&mut shorts);
+    /// rng.fill(&mut wide);
+    /// ```
+    pub fn fill<T: Fillable>(&mut self, dest: &mut [T]) {
+        T::fill_from(self, dest);
+    }
+
+    /// Fills a slice with values from `next_u32`.
+    ///
+    /// The result is identical to calling `next_u32` once per element, in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut rng = Pcg::default();
+    /// let mut values = [0u32; 64];
+    /// rng.fill_u32(&mut values);
+    /// ```
+    #[inline]
+    pub fn fill_u32(&mut self, dest: &mut [u32]) {
+        for value in dest.iter_mut() {
+            *value = self.next_u32();
+        }
+    }
+
+    /// Fills a slice with values from `next_u64`.
+    ///
+    /// The result is identical to calling `next_u64` once per element, in order.
+    #[inline]
+    pub fn fill_u64(&mut self, dest: &mut [u64]) {
+        for value in dest.iter_mut() {
+            *value = self.next_u64();
+        }
+    }
+
+    /// Returns an array of `K` values from `next_u64`, without allocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut rng = Pcg::default();
+    /// let [x, y, z] = rng.gen_array::<3>();
+    /// ```
+    pub fn gen_array<const K: usize>(&mut self) -> [u64; K] {
+        let mut values = [0u64; K];
+        self.fill_u64(&mut values);
+        values
+    }
+
+    /// Returns an array of `K` values from `next_u32`, without allocating.
+    pub fn gen_array_u32<const K: usize>(&mut self) -> [u32; K] {
+        let mut values = [0u32; K];
+        self.fill_u32(&mut values);
+        values
+    }
+
+    /// Fills `buf` with `fill_u64` `iters` times over, for measuring generation throughput.
+    ///
+    /// The buffer is passed through `black_box` after every fill, so the compiler can't skip any
+    /// of the work even though each fill overwrites the last. This gives benchmark harnesses a
+    /// stable workload to time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    /// use std::time::Instant;
+    ///
+    /// let mut rng = Pcg::default();
+    /// let mut buf = [0u64; 1024];
+    /// let start = Instant::now();
+    /// rng.bench_fill(&mut buf, 100);
+    /// let per_value = start.elapsed() / (100 * 1024);
+    /// ```
+    pub fn bench_fill(&mut self, buf: &mut [u64], iters: usize) {
+        for _ in 0..iters {
+            self.fill_u64(buf);
+            black_box(&mut *buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill_u32_matches_next_u32() {
+        let mut a = Pcg::new(42, 54);
+        let mut b = a.clone();
+        let mut values = [0u32; 37];
+        a.fill_u32(&mut values);
+        for &value in values.iter() {
+            assert_eq!(value, b.next_u32());
+        }
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_fill_u64_matches_next_u64() {
+        let mut a = Pcg::new(42, 54);
+        let mut b = a.clone();
+        let mut values = [0u64; 37];
+        a.fill_u64(&mut values);
+        for &value in values.iter() {
+            assert_eq!(value, b.next_u64());
+        }
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_bench_fill_mutates_buffer() {
+        let mut rng = Pcg::new(42, 54);
+        let mut buf = [0u64; 16];
+        rng.bench_fill(&mut buf, 3);
+        assert!(buf.iter().any(|&value| value != 0));
+
+        // The buffer holds the last of the fills
+        let mut expected = Pcg::new(42, 54);
+        expected.advance(2 * 16 * 2);
+        let mut last = [0u64; 16];
+        expected.fill_u64(&mut last);
+        assert_eq!(buf, last);
+        assert_eq!(rng, expected);
+    }
+
+    #[test]
+    fn test_gen_array() {
+        let mut a = Pcg::new(42, 54);
+        let mut b = a.clone();
+        let values = a.gen_array::<4>();
+        for &value in values.iter() {
+            assert_eq!(value, b.next_u64());
+        }
+
+        let values = a.gen_array_u32::<5>();
+        for &value in values.iter() {
+            assert_eq!(value, b.next_u32());
+        }
+
+        assert_eq!(a.gen_array::<0>(), [0u64; 0]);
+        assert_eq!(a.gen_array_u32::<0>(), [0u32; 0]);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_fill_u8_matches_fill_bytes() {
+        let mut a = Pcg::new(42, 54);
+        let mut b = a.clone();
+        let mut values = [0u8; 21];
+        let mut expected = [0u8; 21];
+        a.fill(&mut values);
+        b.fill_bytes(&mut expected);
+        assert_eq!(values, expected);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_fill_u16_matches_fill_bytes() {
+        let mut a = Pcg::new(42, 54);
+        let mut b = a.clone();
+        let mut values = [0u16; 11];
+        let mut bytes = [0u8; 22];
+        a.fill(&mut values);
+        b.fill_bytes(&mut bytes);
+        for (value, pair) in values.iter().zip(bytes.chunks_exact(2)) {
+            assert_eq!(*value, u16::from_le_bytes([pair[0], pair[1]]));
+        }
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_fill_u32_and_u64_match_dedicated_fills() {
+        let mut a = Pcg::new(42, 54);
+        let mut b = a.clone();
+        let mut values = [0u32; 9];
+        let mut expected = [0u32; 9];
+        a.fill(&mut values);
+        b.fill_u32(&mut expected);
+        assert_eq!(values, expected);
+
+        let mut values = [0u64; 9];
+        let mut expected = [0u64; 9];
+        a.fill(&mut values);
+        b.fill_u64(&mut expected);
+        assert_eq!(values, expected);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_fill_u128_matches_next_u128() {
+        let mut a = Pcg::new(42, 54);
+        let mut b = a.clone();
+        let mut values = [0u128; 5];
+        a.fill(&mut values);
+        for &value in values.iter() {
+            assert_eq!(value, b.next_u128());
+        }
+        assert_eq!(a, b);
+    }
+}
@@ -0,0 +1,228 @@
+//! Text formatting and parsing for `Pcg`.
+//!
+//! The compact hex form, `state:inc`, is produced by the `LowerHex` and `UpperHex` impls and can
+//! be parsed back into a `Pcg` with `FromStr`.
+
+use crate::{OutputFn, Pcg};
+
+#[cfg(feature = "std")]
+use std::{fmt, num::ParseIntError, str::FromStr};
+
+#[cfg(not(feature = "std"))]
+use core::{fmt, num::ParseIntError, str::FromStr};
+
+/// The separator between the state and the increment in the compact hex form
+const SEPARATOR: char = ':';
+
+impl<O: OutputFn> fmt::Display for Pcg<O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Pcg {{ state: {:#018x}, inc: {:#018x} }}",
+            self.state, self.inc
+        )
+    }
+}
+
+impl<O: OutputFn> fmt::LowerHex for Pcg<O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:016x}{}{:016x}", self.state, SEPARATOR, self.inc)
+    }
+}
+
+impl<O: OutputFn> fmt::UpperHex for Pcg<O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:016X}{}{:016X}", self.state, SEPARATOR, self.inc)
+    }
+}
+
+/// An error that can occur when parsing a `Pcg` from its compact hex form
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ParsePcgError {
+    /// The input did not contain the `:` separating the state and the increment
+    MissingSeparator,
+    /// The state was not a valid 64-bit hexadecimal integer
+    InvalidState(ParseIntError),
+    /// The increment was not a valid 64-bit hexadecimal integer
+    InvalidIncrement(ParseIntError),
+    /// The increment was even, which would not give the generator a full period
+    EvenIncrement,
+}
+
+impl fmt::Display for ParsePcgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParsePcgError::MissingSeparator => {
+                write!(f, "expected a `state{}inc` hex pair", SEPARATOR)
+            }
+            ParsePcgError::InvalidState(e) => write!(f, "invalid hex state: {}", e),
+            ParsePcgError::InvalidIncrement(e) => write!(f, "invalid hex increment: {}", e),
+            ParsePcgError::EvenIncrement => write!(f, "the increment must be odd"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParsePcgError {}
+
+impl FromStr for Pcg {
+    type Err = ParsePcgError;
+
+    /// Parses a generator from the `state:inc` hex pair emitted by the `LowerHex` and `UpperHex`
+    /// impls
+    ///
+    /// Both halves must be 64-bit integers written with hex digits only, with no sign or prefix,
+    /// and the increment must be odd.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let rng: Pcg = "853c49e6748fea9b:da3e39cb94b95bdb".parse().unwrap();
+    /// assert_eq!(rng, Pcg::default());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (state, inc) = s
+            .split_once(SEPARATOR)
+            .ok_or(ParsePcgError::MissingSeparator)?;
+        let state = parse_hex(state).map_err(ParsePcgError::InvalidState)?;
+        let inc = parse_hex(inc).map_err(ParsePcgError::InvalidIncrement)?;
+
+        if inc & 1 == 0 {
+            return Err(ParsePcgError::EvenIncrement);
+        }
+        Ok(Pcg::from_state(state, inc))
+    }
+}
+
+/// Parses one half of the compact hex form, which must contain only ASCII hex digits
+///
+/// `u64::from_str_radix` also accepts a leading `+`, which the hex impls never print, so input with
+/// any character other than a hex digit is rejected before parsing. `ParseIntError` can't be
+/// constructed directly, so the error comes from parsing a lone `-`, which reports an invalid digit
+/// just like `from_str_radix` does for other stray characters.
+fn parse_hex(field: &str) -> Result<u64, ParseIntError> {
+    if field.bytes().all(|b| b.is_ascii_hexdigit()) {
+        u64::from_str_radix(field, 16)
+    } else {
+        u64::from_str_radix("-", 16)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "std")]
+    use std::num::IntErrorKind;
+
+    #[cfg(not(feature = "std"))]
+    use core::num::IntErrorKind;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            Pcg::default().to_string(),
+            "Pcg { state: 0x853c49e6748fea9b, inc: 0xda3e39cb94b95bdb }"
+        );
+    }
+
+    #[test]
+    fn test_hex() {
+        let rng = Pcg::default();
+        assert_eq!(format!("{:x}", rng), "853c49e6748fea9b:da3e39cb94b95bdb");
+        assert_eq!(format!("{:X}", rng), "853C49E6748FEA9B:DA3E39CB94B95BDB");
+    }
+
+    #[test]
+    fn test_hex_roundtrip() {
+        let rng = Pcg::new(42, 54);
+        assert_eq!(format!("{:x}", rng).parse::<Pcg>(), Ok(rng.clone()));
+        assert_eq!(format!("{:X}", rng).parse::<Pcg>(), Ok(rng));
+    }
+
+    #[test]
+    fn test_parse_roundtrip_many() {
+        let mut rng = Pcg::new(42, 54);
+        for _ in 0..100 {
+            let parsed: Pcg = format!("{:x}", rng).parse().unwrap();
+            assert_eq!(parsed, rng);
+            rng.advance(0x1234_5678);
+        }
+    }
+
+    #[test]
+    fn test_parse_missing_separator() {
+        assert_eq!(
+            "853c49e6748fea9b".parse::<Pcg>(),
+            Err(ParsePcgError::MissingSeparator)
+        );
+        assert_eq!("".parse::<Pcg>(), Err(ParsePcgError::MissingSeparator));
+    }
+
+    #[test]
+    fn test_parse_invalid_hex() {
+        assert!(matches!(
+            "xyz:da3e39cb94b95bdb".parse::<Pcg>(),
+            Err(ParsePcgError::InvalidState(_))
+        ));
+        assert!(matches!(
+            "853c49e6748fea9b:xyz".parse::<Pcg>(),
+            Err(ParsePcgError::InvalidIncrement(_))
+        ));
+        // Too large to fit in 64 bits
+        assert!(matches!(
+            "1853c49e6748fea9b:da3e39cb94b95bdb".parse::<Pcg>(),
+            Err(ParsePcgError::InvalidState(_))
+        ));
+        assert!(matches!(
+            "853c49e6748fea9b:".parse::<Pcg>(),
+            Err(ParsePcgError::InvalidIncrement(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_rejects_sign() {
+        assert!(matches!(
+            "+2a:37".parse::<Pcg>(),
+            Err(ParsePcgError::InvalidState(ref e)) if *e.kind() == IntErrorKind::InvalidDigit
+        ));
+        assert!(matches!(
+            "2a:+37".parse::<Pcg>(),
+            Err(ParsePcgError::InvalidIncrement(ref e)) if *e.kind() == IntErrorKind::InvalidDigit
+        ));
+        assert!(matches!(
+            "+2a:+37".parse::<Pcg>(),
+            Err(ParsePcgError::InvalidState(_))
+        ));
+        assert!(matches!(
+            "-2a:37".parse::<Pcg>(),
+            Err(ParsePcgError::InvalidState(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_empty_field() {
+        assert!(matches!(
+            ":37".parse::<Pcg>(),
+            Err(ParsePcgError::InvalidState(ref e)) if *e.kind() == IntErrorKind::Empty
+        ));
+        assert!(matches!(
+            "2a:".parse::<Pcg>(),
+            Err(ParsePcgError::InvalidIncrement(ref e)) if *e.kind() == IntErrorKind::Empty
+        ));
+        assert!(matches!(
+            ":".parse::<Pcg>(),
+            Err(ParsePcgError::InvalidState(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_even_increment() {
+        assert_eq!(
+            "853c49e6748fea9b:da3e39cb94b95bda".parse::<Pcg>(),
+            Err(ParsePcgError::EvenIncrement)
+        );
+    }
+}
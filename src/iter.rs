@@ -0,0 +1,138 @@
+//! Iterator adapters that yield an endless stream of values from a `Pcg`.
+
+use crate::{OutputFn, Pcg, XshRr};
+use rand_core::RngCore;
+
+/// An endless iterator of `u32` values, created by `Pcg::iter_u32`
+#[derive(Debug)]
+pub struct IterU32<'a, O = XshRr> {
+    rng: &'a mut Pcg<O>,
+}
+
+/// An endless iterator of `u64` values, created by `Pcg::iter_u64`
+#[derive(Debug)]
+pub struct IterU64<'a, O = XshRr> {
+    rng: &'a mut Pcg<O>,
+}
+
+/// An endless iterator of bytes, created by `Pcg::iter_bytes`
+///
+/// Bytes are taken from successive `next_u64` values in little-endian order.
+#[derive(Debug)]
+pub struct IterBytes<'a, O = XshRr> {
+    rng: &'a mut Pcg<O>,
+    buf: [u8; 8],
+    pos: usize,
+}
+
+impl<O: OutputFn> Pcg<O> {
+    /// Returns an endless iterator that yields values from `next_u32`
+    pub fn iter_u32(&mut self) -> IterU32<'_, O> {
+        IterU32 { rng: self }
+    }
+
+    /// Returns an endless iterator that yields values from `next_u64`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut rng = Pcg::default();
+    /// let values: Vec<u64> = rng.iter_u64().take(10).collect();
+    /// assert_eq!(values.len(), 10);
+    /// ```
+    pub fn iter_u64(&mut self) -> IterU64<'_, O> {
+        IterU64 { rng: self }
+    }
+
+    /// Returns an endless iterator of bytes, which is handy for generating fuzzing inputs
+    ///
+    /// Each `next_u64` value is split into 8 bytes in little-endian order. A new value is only
+    /// generated once all 8 bytes of the previous one have been yielded.
+    pub fn iter_bytes(&mut self) -> IterBytes<'_, O> {
+        IterBytes {
+            rng: self,
+            buf: [0; 8],
+            pos: 8,
+        }
+    }
+}
+
+impl<O: OutputFn> Iterator for IterU32<'_, O> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        Some(self.rng.next_u32())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl<O: OutputFn> Iterator for IterU64<'_, O> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        Some(self.rng.next_u64())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl<O: OutputFn> Iterator for IterBytes<'_, O> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.pos == self.buf.len() {
+            self.buf = self.rng.next_u64().to_le_bytes();
+            self.pos = 0;
+        }
+        let byte = self.buf[self.pos];
+        self.pos += 1;
+        Some(byte)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iter_u64_matches_next_u64() {
+        let mut a = Pcg::new(42, 54);
+        let mut b = a.clone();
+        let values: Vec<u64> = a.iter_u64().take(20).collect();
+        let expected: Vec<u64> = (0..20).map(|_| b.next_u64()).collect();
+        assert_eq!(values, expected);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_iter_u32_matches_next_u32() {
+        let mut a = Pcg::new(42, 54);
+        let mut b = a.clone();
+        let values: Vec<u32> = a.iter_u32().take(20).collect();
+        let expected: Vec<u32> = (0..20).map(|_| b.next_u32()).collect();
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn test_iter_bytes() {
+        let mut a = Pcg::new(42, 54);
+        let mut b = a.clone();
+        let bytes: Vec<u8> = a.iter_bytes().take(12).collect();
+
+        let first = b.next_u64().to_le_bytes();
+        let second = b.next_u64().to_le_bytes();
+        assert_eq!(&bytes[..8], &first);
+        assert_eq!(&bytes[8..], &second[..4]);
+    }
+}
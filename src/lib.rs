@@ -20,43 +20,155 @@
 //! let mut some_f32: f32 = pcg.gen();
 //! let mut some_u32: u32 = pcg.gen();
 //! ```
+//!
+//! `Pcg` natively produces 32-bit values. If you need full-entropy 64-bit values, use `Pcg64`,
+//! which is backed by 128 bits of state.
+//!
+//! # Reproducibility
+//!
+//! Output is identical across platforms. In particular, `fill_bytes` writes each 64-bit value
+//! in little-endian order, so filling a buffer from a given seed produces the same bytes on
+//! little- and big-endian machines.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
-use crate::consts::{INCREMENTOR, INIT_INC, INIT_STATE};
+use crate::consts::{INCREMENTOR, INCREMENTOR_INVERSE, INIT_INC, INIT_STATE, ONESEQ_INC};
+use crate::mix::mix64;
 
 #[cfg(feature = "std")]
 use std::{
+    array::TryFromSliceError,
+    convert::TryFrom,
+    fmt,
     hash::{Hash, Hasher},
-    num::Wrapping,
+    marker::PhantomData,
 };
 
 #[cfg(not(feature = "std"))]
 use core::{
+    array::TryFromSliceError,
+    convert::TryFrom,
+    fmt,
     hash::{Hash, Hasher},
-    num::Wrapping,
+    marker::PhantomData,
 };
 
-use rand_core::{impls, Error, RngCore, SeedableRng};
+use rand_core::{Error, RngCore, SeedableRng};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+mod buffered;
+mod builder;
 mod consts;
+#[cfg(feature = "defmt")]
+mod defmt_impls;
+mod diagnostics;
+mod distributions;
+#[cfg(feature = "std")]
+mod dump;
+mod engine;
+mod ext;
+mod fill;
+mod format;
+mod iter;
+mod mcg;
+mod mix;
+pub mod output;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod pcg64;
+#[cfg(feature = "rand_core_0_9")]
+mod rand_core_0_9_impls;
+mod sample;
+mod seq;
+#[cfg(feature = "serde")]
+mod serde_impls;
+#[cfg(feature = "simd")]
+mod simd;
+#[cfg(feature = "subtle")]
+mod subtle_impls;
+#[cfg(feature = "std")]
+mod thread;
+#[cfg(target_has_atomic = "64")]
+mod unique;
+#[cfg(feature = "uuid")]
+mod uuid_v4;
+#[cfg(feature = "zeroize")]
+mod zeroize_impls;
+
+pub use crate::buffered::BufferedPcg;
+pub use crate::builder::PcgBuilder;
+#[cfg(feature = "std")]
+pub use crate::distributions::AliasTable;
+pub use crate::distributions::{AliasTableError, Bernoulli, BernoulliError};
+pub use crate::engine::PcgEngine;
+pub use crate::ext::PcgExt;
+pub use crate::fill::Fillable;
+pub use crate::format::ParsePcgError;
+pub use crate::iter::{IterBytes, IterU32, IterU64};
+pub use crate::mcg::PcgMcg;
+pub use crate::output::{OutputFn, PcgRxsMXs, XshRr};
+pub use crate::pcg64::Pcg64;
+#[cfg(feature = "std")]
+pub use crate::thread::thread_pcg;
 
 /// The `Pcg` state struct contains state information for use by the random
 /// number generating functions.
 ///
 /// The internals are private and shouldn't be modified by
-/// anything other than the member functions. Note that the random number
+/// anything other than the member functions. They can be read with `state` and `increment` for
+/// checkpointing, and restored with `from_state`. Note that the random number
 /// generating functions will modify the state of this struct, so you must
 /// initialize `Pcg` as mutable in order to use any of its functionality.
-#[derive(Debug, Clone, Eq, PartialEq)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct Pcg {
+///
+/// A `Pcg` also remembers the state it was constructed with so that it can be rewound with
+/// `reset`. Two generators compare (and hash) equal when they have the same current state and
+/// increment, regardless of where they started or whether `next_normal` has a deviate cached.
+///
+/// The increment is always odd, since the LCG only has a full period when it is. Every way of
+/// creating a `Pcg` from a raw increment, such as `from_state`, `from_bytes` and deserialization,
+/// forces its lowest bit to 1, except for parsing with `FromStr`, which rejects an even increment.
+///
+/// The output permutation is selected with the `O` type parameter, which defaults to the XSH RR
+/// permutation of the reference `pcg32`. The other permutations in the `output` module can be
+/// selected with `with_output`.
+#[derive(Clone)]
+pub struct Pcg<O = XshRr> {
     state: u64,
     inc: u64,
+    init_state: u64,
+    init_inc: u64,
+    /// The second normal deviate produced by the last Box-Muller transform in `next_normal`
+    #[cfg(feature = "std")]
+    cached_normal: Option<f64>,
+    /// The number of values produced by `next_u32` and `next_u64`
+    #[cfg(feature = "trace")]
+    count: u64,
+    output: PhantomData<O>,
 }
 
+/// The PCG generator with 64 bits of state and 32 bits of output, the reference `pcg32`
+///
+/// This is the same type as `Pcg`, named to make its width explicit alongside `Pcg64`.
+pub type Pcg32 = Pcg;
+
 impl Pcg {
+    /// The generator returned by `Pcg::default`, available in `const` contexts
+    pub const DEFAULT: Pcg = Pcg::from_parts(INIT_STATE, INIT_INC);
+
+    /// The base-2 logarithm of the generator's period.
+    ///
+    /// The underlying LCG is full-period for any odd increment, which every constructor
+    /// guarantees, so every stream visits all `2^64` states before repeating. A 64-bit value
+    /// from a 32-bit permutation takes two steps, so half as many of those can be drawn. The
+    /// period doesn't depend on the output permutation, so `period_log2` returns this for every
+    /// `Pcg<O>`.
+    pub const PERIOD_LOG2: u32 = 64;
+
+    /// The number of steps `jumped` advances by, `2^32`, for every `Pcg<O>`
+    pub const JUMP: u64 = 1 << 32;
+
     /// Constructs a new PCG state struct with a particular seed and sequence.
     ///
     /// The function returns a struct with state information for the PCG RNG.  The `seed` param
@@ -64,6 +176,9 @@ impl Pcg {
     /// ID. If you're unsure of which params to initialize this struct with, construct the default
     /// struct.
     ///
+    /// This follows the seeding procedure of the reference `pcg32_srandom_r`, so a generator
+    /// constructed with a given `seed` and `seq` produces the same stream as the C library.
+    ///
     /// If you can't think of a seed and a state to initialize this with, just use the default
     /// struct.
     ///
@@ -74,186 +189,1946 @@ impl Pcg {
     ///
     /// let mut rng = Pcg::new(0, 0);
     /// ```
-    pub fn new(seed: u64, seq: u64) -> Pcg {
-        Pcg {
-            state: seed,
-            inc: (seq << 1) | 1,
-        }
+    ///
+    /// This is a `const fn`, so it can be used to initialize constants and statics:
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// const RNG: Pcg = Pcg::new(1, 2);
+    /// # assert_eq!(RNG, Pcg::new(1, 2));
+    /// ```
+    pub const fn new(seed: u64, seq: u64) -> Pcg {
+        // The state is stepped as a plain integer, since a temporary `Pcg` can't be dropped in a
+        // `const fn` when the `zeroize-on-drop` feature gives it a destructor
+        let inc = (seq << 1) | 1;
+        let state = next_state(0, inc).wrapping_add(seed);
+        Pcg::from_parts(next_state(state, inc), inc)
     }
-}
 
-impl Default for Pcg {
-    fn default() -> Self {
-        Pcg {
-            state: INIT_STATE,
-            inc: INIT_INC,
-        }
+    /// Constructs a new generator on a fixed stream, so only a seed is needed.
+    ///
+    /// This matches the reference `pcg32_oneseq` generator, which always uses the increment
+    /// `1442695040888963407`. It's the simplest way to get a seeded generator when there's no
+    /// need for separate streams.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut rng = Pcg::new_oneseq(42);
+    /// assert_eq!(rng.increment(), 1442695040888963407);
+    /// ```
+    pub const fn new_oneseq(seed: u64) -> Pcg {
+        Pcg::new(seed, ONESEQ_INC >> 1)
     }
-}
 
-impl RngCore for Pcg {
-    fn next_u32(&mut self) -> u32 {
-        self.next_u64() as u32
+    /// Returns `count` generators that share a seed but are each on their own stream.
+    ///
+    /// The generators use the sequence IDs `0..count`, so each one has a unique odd increment.
+    /// Streams with different increments never overlap over the full `2^64` period, which makes
+    /// this a convenient way to hand out independent generators to parallel workers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let workers: Vec<Pcg> = Pcg::streams(42, 4).collect();
+    /// assert_eq!(workers[0], Pcg::new(42, 0));
+    /// assert_eq!(workers[3], Pcg::new(42, 3));
+    /// ```
+    pub fn streams(seed: u64, count: usize) -> impl Iterator<Item = Pcg> {
+        (0..count as u64).map(move |seq| Pcg::new(seed, seq))
     }
 
-    fn next_u64(&mut self) -> u64 {
-        let old_state = self.state;
-        self.state = (Wrapping(old_state) * Wrapping(INCREMENTOR) + Wrapping(self.inc)).0;
-        let xor_shifted = (old_state >> 18) ^ old_state >> 27;
-
-        // need to cast to i64 to allow the `-` operator (casting between integers of
-        // the same size is a no-op)
-        let rot = (old_state >> 59) as i64;
-        (xor_shifted >> rot as u64) | (xor_shifted << ((-rot) & 31))
+    /// Consumes the generator and returns an iterator of generators on the streams `0, 1, 2, ...`,
+    /// all seeded with this generator's seed.
+    ///
+    /// The seed is the one the generator was constructed with, recovered from the state that
+    /// `reset` rewinds to, so the yielded generators don't depend on how far this one has
+    /// advanced. Every yielded generator has its own increment, which keeps their streams
+    /// distinct. There are `2^63` distinct streams, so the iterator ends after yielding that many,
+    /// which is unreachable in practice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut streams = Pcg::new(42, 54).into_stream_iter();
+    /// assert_eq!(streams.next(), Some(Pcg::new(42, 0)));
+    /// assert_eq!(streams.next(), Some(Pcg::new(42, 1)));
+    /// ```
+    pub fn into_stream_iter(self) -> impl Iterator<Item = Pcg> {
+        let seed = unseed(self.init_state, self.init_inc);
+        (0..=u64::MAX >> 1).map(move |seq| Pcg::new(seed, seq))
     }
 
-    fn fill_bytes(&mut self, dest: &mut [u8]) {
-        impls::fill_bytes_via_next(self, dest)
-    }
+    /// Constructs a new PCG state struct seeded from operating system entropy.
+    ///
+    /// Both the seed and the stream are drawn from the OS random number source via `getrandom`,
+    /// so every call yields an unpredictable, non-reproducible generator. This requires the
+    /// `getrandom` feature.
+    ///
+    /// On `wasm32-unknown-unknown` there is no operating system to ask, so `getrandom` needs to be
+    /// told to get entropy from the JavaScript environment by enabling its `wasm-bindgen` feature
+    /// in the final binary, as described in the README.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the operating system fails to provide entropy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut rng = Pcg::from_entropy();
+    /// ```
+    #[cfg(feature = "getrandom")]
+    pub fn from_entropy() -> Pcg {
+        let mut bytes = [0u8; 16];
+        getrandom::getrandom(&mut bytes).expect("failed to obtain entropy from the OS");
 
-    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
-        self.fill_bytes(dest);
-        Ok(())
+        let mut seed = [0u8; 8];
+        let mut seq = [0u8; 8];
+        seed.copy_from_slice(&bytes[..8]);
+        seq.copy_from_slice(&bytes[8..]);
+        Pcg::new(u64::from_be_bytes(seed), u64::from_be_bytes(seq))
     }
-}
 
-/// The number of 8-bit buckets that the seed is made of
-const N: usize = 8;
+    /// Constructs a PCG state struct directly from a raw state and increment.
+    ///
+    /// Unlike `new`, this does not run the seeding procedure, so it can be used to restore a
+    /// generator from values previously read with `state` and `increment`. The LCG only has a full
+    /// period when the increment is odd, so the lowest bit of `inc` is forced to 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let rng = Pcg::new(42, 54);
+    /// let restored = Pcg::from_state(rng.state(), rng.increment());
+    /// assert_eq!(rng, restored);
+    /// ```
+    pub fn from_state(state: u64, inc: u64) -> Pcg {
+        Pcg::from_parts(state, inc | 1)
+    }
 
-/// A wrapper type for the PcgSeed
-///
-/// This wrapper allows us to implement a `SeedableRng` for `Pcg`. There are also conversion traits
-/// defined so that you can switch between `PcgSeed` and `U64` easily. The lowest bit in the lowest
-/// index of the underlying array corresponds to the most significant bit in the converted `U64`.
-///
-/// For example: `[0, 1, 2, 3, 4, 5, 6, 7]` corresponds to `01234567` when converted to the packged
-/// unsigned integer representation.
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct PcgSeed(pub [u8; N]);
+    /// Restores a generator from the 16 bytes produced by `to_bytes`.
+    ///
+    /// As with `from_state`, the lowest bit of the increment is forced to 1.
+    pub fn from_bytes(bytes: [u8; 16]) -> Pcg {
+        let mut state = [0u8; 8];
+        let mut inc = [0u8; 8];
+        state.copy_from_slice(&bytes[..8]);
+        inc.copy_from_slice(&bytes[8..]);
+        Pcg::from_state(u64::from_be_bytes(state), u64::from_be_bytes(inc))
+    }
 
-/// A wrapper type for u64 so we can define methods on a built-in primitive
-///
-/// This enables, amongst other things, conversions from `u64` to `PcgSeed`.
-#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct U64(pub u64);
+    /// Constructs a generator from 16 bytes of another generator's output.
+    ///
+    /// The first 8 bytes become the state and the last 8 become the increment, with its lowest
+    /// bit forced to 1. Unlike `SeedableRng::from_rng`, this uses `fill_bytes` rather than
+    /// `try_fill_bytes`, so it doesn't return a `Result`. A source that can fail will panic
+    /// instead, as `fill_bytes` does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut parent = Pcg::new(42, 54);
+    /// let child = Pcg::from_rng(&mut parent);
+    /// ```
+    pub fn from_rng<R: RngCore>(source: &mut R) -> Pcg {
+        let mut bytes = [0u8; 16];
+        source.fill_bytes(&mut bytes);
+        Pcg::from_bytes(bytes)
+    }
+}
 
-/// A bit mask for u8
-const MASK: u8 = 0b11111111;
+impl<O: OutputFn> Pcg<O> {
+    /// Returns the base-2 logarithm of the generator's period, `PERIOD_LOG2`.
+    pub fn period_log2(&self) -> u32 {
+        Pcg::PERIOD_LOG2
+    }
 
-impl From<PcgSeed> for U64 {
-    fn from(seed: PcgSeed) -> Self {
-        let mut res: u64 = 0;
+    /// Constructs a generator whose starting point is the given state and increment
+    const fn from_parts(state: u64, inc: u64) -> Pcg<O> {
+        Pcg {
+            state,
+            inc,
+            init_state: state,
+            init_inc: inc,
+            #[cfg(feature = "std")]
+            cached_normal: None,
+            #[cfg(feature = "trace")]
+            count: 0,
+            output: PhantomData,
+        }
+    }
 
-        // We iterate through the array of bytes, packing them into a u64 by filling in a
-        // byte-sized section at a time
-        for (i, &byte) in seed.0.iter().enumerate() {
-            // We have to subtract from the index because the 0th index of the array corresponds to
-            // the most significant bit (MSB). If the array is [0, 1, 2, 3], we want the resulting
-            // integer to look like 0123.
-            let shift_up = (N - i - 1) * 8;
-            let byte = byte as u64;
-            let block = (byte << shift_up) as u64;
-            res |= block;
+    /// Changes the output permutation of the generator, keeping its state and stream
+    ///
+    /// Constructors are only provided for the default `XshRr` permutation, so this is how a
+    /// generator with a different permutation is created.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::output::RxsMXs;
+    /// use pcg::Pcg;
+    ///
+    /// let rng: Pcg<RxsMXs> = Pcg::new(42, 54).with_output();
+    /// ```
+    pub fn with_output<P: OutputFn>(self) -> Pcg<P> {
+        Pcg {
+            state: self.state,
+            inc: self.inc,
+            init_state: self.init_state,
+            init_inc: self.init_inc,
+            #[cfg(feature = "std")]
+            cached_normal: None,
+            #[cfg(feature = "trace")]
+            count: self.count,
+            output: PhantomData,
         }
-        U64(res)
     }
-}
 
-impl Default for PcgSeed {
-    fn default() -> Self {
-        Self([0; N])
+    /// Derives a new child generator from this one.
+    ///
+    /// The child's seed and stream are drawn from the parent's output, which advances the parent
+    /// so the same split never happens twice. Given the parent's initial seed, a fixed sequence of
+    /// splits is fully reproducible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut parent = Pcg::new(42, 54);
+    /// let a = parent.split();
+    /// let b = parent.split();
+    /// assert_ne!(a, b);
+    /// ```
+    pub fn split(&mut self) -> Pcg<O> {
+        let seed = self.next_u64();
+        let seq = self.next_u64();
+        Pcg::new(seed, seq).with_output()
     }
-}
 
-impl AsMut<[u8]> for PcgSeed {
-    fn as_mut(&mut self) -> &mut [u8] {
-        &mut self.0
+    /// Serializes the generator into 16 bytes: the state followed by the increment, both
+    /// big-endian.
+    ///
+    /// The byte order is fixed, so the output can be persisted and restored with `from_bytes` on
+    /// any architecture.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let rng = Pcg::new(42, 54);
+    /// assert_eq!(Pcg::from_bytes(rng.to_bytes()), rng);
+    /// ```
+    pub fn to_bytes(&self) -> [u8; 16] {
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&self.state.to_be_bytes());
+        bytes[8..].copy_from_slice(&self.inc.to_be_bytes());
+        bytes
     }
-}
 
-impl Hash for PcgSeed {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        // create a vector from the array
-        let seed_vec = self.0.to_vec();
-        seed_vec.hash(state);
+    /// Returns the current internal state of the generator
+    pub fn state(&self) -> u64 {
+        self.state
     }
-}
 
-impl From<u64> for PcgSeed {
-    fn from(init: u64) -> Self {
-        let mut seed: [u8; N] = [0; N];
+    /// Returns the increment of the generator, which determines its stream
+    pub fn increment(&self) -> u64 {
+        self.inc
+    }
 
-        for i in 0..N {
-            let shift_factor = (N - i - 1) * 8;
-            let section = (init >> shift_factor) as u8;
-            seed[i] = section & MASK;
-        }
-        PcgSeed(seed)
+    /// Returns the value the next call to `next_u32` will produce, without advancing the
+    /// generator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    /// use rand_core::RngCore;
+    ///
+    /// let mut rng = Pcg::new(42, 54);
+    /// let peeked = rng.peek_u32();
+    /// assert_eq!(rng.next_u32(), peeked);
+    /// ```
+    pub fn peek_u32(&self) -> u32 {
+        self.clone().next_u32()
     }
-}
 
-impl From<U64> for PcgSeed {
-    fn from(init: U64) -> Self {
-        init.0.into()
+    /// Returns the value the next call to `next_u64` will produce, without advancing the
+    /// generator.
+    pub fn peek_u64(&self) -> u64 {
+        self.clone().next_u64()
     }
-}
 
-impl SeedableRng for Pcg {
-    type Seed = PcgSeed;
+    /// Returns the number of values this generator has produced with `next_u32` and `next_u64`,
+    /// including the calls made by other methods such as `fill_bytes`.
+    ///
+    /// The count is carried over by `with_output`, and isn't changed by methods that reposition
+    /// the generator, such as `reset` and `advance`. Only available with the `trace` feature.
+    #[cfg(feature = "trace")]
+    pub fn generated_count(&self) -> u64 {
+        self.count
+    }
 
-    fn from_seed(seed: Self::Seed) -> Pcg {
-        Pcg::new(U64::from(seed).0, INIT_INC)
+    /// Overwrites the internal state of the generator, leaving the increment (and therefore the
+    /// stream) untouched
+    ///
+    /// This does not change the starting point that `reset` rewinds to.
+    pub fn set_state(&mut self, state: u64) {
+        self.state = state;
+        self.discard_cached();
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fmt::{Binary, Debug};
+    /// Returns the stream ID of the generator, which is the `seq` param it was constructed with
+    pub fn stream(&self) -> u64 {
+        self.inc >> 1
+    }
 
-    /// A helper function to compare variables of a type that can be represented as a binary string
+    /// Switches the generator to another stream without changing its state.
     ///
-    /// This provides more helpful/convenient error messages which will show the binary
-    /// representations of integers rather than the base 10 representations.
-    fn assert_eq_binary<T>(a: T, b: T)
-    where
-        T: PartialEq + Debug + Binary,
-    {
-        let b_str_a = format!("{:b}", a);
-        let b_str_b = format!("{:b}", b);
-        assert_eq!(b_str_a, b_str_b);
+    /// The increment is recomputed from `seq` the same way `new` computes it. The state counter
+    /// is kept, but since the increment feeds into every step, the output sequence changes from
+    /// this point forward.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut rng = Pcg::new(42, 54);
+    /// rng.set_stream(7);
+    /// assert_eq!(rng.stream(), 7);
+    /// ```
+    pub fn set_stream(&mut self, seq: u64) {
+        self.inc = (seq << 1) | 1;
+        self.discard_cached();
     }
 
-    #[test]
-    fn test_seed_to_u64() {
-        let mut seed = PcgSeed::default();
-        seed.0[0] = MASK;
+    /// Restores the generator to the state it had immediately after it was constructed.
+    ///
+    /// This rewinds any calls to `next_u32`, `next_u64`, or `advance`, so the generator replays
+    /// the exact same sequence, which is handy for repeated deterministic trials. Generators
+    /// restored with `from_state` or `from_bytes` rewind to the state they were restored with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    /// use rand_core::RngCore;
+    ///
+    /// let mut rng = Pcg::new(42, 54);
+    /// let first = rng.next_u64();
+    /// rng.reset();
+    /// assert_eq!(rng.next_u64(), first);
+    /// ```
+    pub fn reset(&mut self) {
+        self.state = self.init_state;
+        self.inc = self.init_inc;
+        self.discard_cached();
+    }
 
-        let converted_int = U64::from(seed);
-        let expected = (MASK as u64) << ((N - 1) * 8);
-        assert_eq_binary(converted_int.0, expected);
+    /// Advances the generator by `delta` steps in `O(log delta)` time.
+    ///
+    /// Each step produces one output of the permutation. With the default 32-bit XSH RR
+    /// permutation this repositions the internal state as if `delta` values had been generated
+    /// with `next_u32`, and since `next_u64` is built from two 32-bit outputs, it consumes two
+    /// steps per call. This is useful for splitting a single stream across parallel workers without
+    /// iterating through the skipped values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    /// use rand_core::RngCore;
+    ///
+    /// let mut a = Pcg::new(42, 54);
+    /// let mut b = a.clone();
+    ///
+    /// a.advance(3);
+    /// for _ in 0..3 {
+    ///     b.next_u32();
+    /// }
+    /// assert_eq!(a, b);
+    /// ```
+    pub fn advance(&mut self, delta: u64) {
+        let (mult, plus) = self.jump(delta);
+        self.state = mult.wrapping_mul(self.state).wrapping_add(plus);
+        self.discard_cached();
+    }
 
-        let mut seed = PcgSeed::default();
-        seed.0[N - 1] = MASK;
-        let converted_int = U64::from(seed);
-        let expected = MASK as u64;
-        assert_eq_binary(converted_int.0, expected)
+    /// Moves the generator backward by `delta` steps in `O(log delta)` time.
+    ///
+    /// The LCG has a full period of `2^64`, so stepping backward is the same as advancing by
+    /// `2^64 - delta`. Likewise, advancing by `u64::MAX` wraps around to a single step back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    /// use rand_core::RngCore;
+    ///
+    /// let mut rng = Pcg::new(42, 54);
+    /// let first = rng.next_u32();
+    /// rng.backstep(1);
+    /// assert_eq!(rng.next_u32(), first);
+    /// ```
+    pub fn backstep(&mut self, delta: u64) {
+        self.advance(delta.wrapping_neg());
     }
 
-    #[test]
-    fn test_u64_to_seed() {
-        let integer = (MASK as u64) << ((N - 1) * 8);
-        let seed = PcgSeed::from(integer);
-        let mut expected = PcgSeed::default();
-        expected.0[0] = MASK;
-        assert_eq!(seed, expected);
+    /// Skips `n` outputs in `O(log n)` time, like `std::linear_congruential_engine::discard` in
+    /// C++.
+    ///
+    /// This is the same as `advance`, so it is equivalent to calling `next_u32` `n` times, or
+    /// `next_u64` `n` times when the permutation produces 64 bits. For the 32-bit permutations,
+    /// each `next_u64` takes two steps, so skipping `k` of them takes `discard(2 * k)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    /// use rand_core::RngCore;
+    ///
+    /// let mut rng = Pcg::new(42, 54);
+    /// let mut other = rng.clone();
+    /// rng.discard(3);
+    /// for _ in 0..3 {
+    ///     other.next_u32();
+    /// }
+    /// assert_eq!(rng, other);
+    /// ```
+    pub fn discard(&mut self, n: u64) {
+        self.advance(n);
+    }
 
-        let integer = MASK as u64;
-        let seed = PcgSeed::from(integer);
-        let mut expected = PcgSeed::default();
-        expected.0[N - 1] = MASK;
-        assert_eq!(seed, expected);
+    /// Returns a clone of the generator advanced by `n` steps, leaving `self` unchanged.
+    ///
+    /// As with `advance`, a step is a single `next_u32`, while each `next_u64` takes two steps
+    /// unless the permutation produces 64 bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    /// use rand_core::RngCore;
+    ///
+    /// let rng = Pcg::new(42, 54);
+    /// let mut speculative = rng.clone();
+    /// speculative.next_u32();
+    /// assert_eq!(rng.clone_advance(1), speculative);
+    /// ```
+    pub fn clone_advance(&self, n: u64) -> Pcg<O> {
+        let mut rng = self.clone();
+        rng.advance(n);
+        rng
+    }
+
+    /// Returns a clone of the generator advanced by `JUMP` steps, leaving `self` unchanged.
+    ///
+    /// Chaining calls gives a sequence of non-overlapping substreams of `2^32` steps each, which
+    /// is handy for handing out generators to parallel workers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let rng = Pcg::new(42, 54);
+    /// let worker_rngs: Vec<Pcg> = (0..4)
+    ///     .scan(rng, |rng, _| {
+    ///         *rng = rng.jumped();
+    ///         Some(rng.clone())
+    ///     })
+    ///     .collect();
+    /// ```
+    pub fn jumped(&self) -> Pcg<O> {
+        self.clone_advance(Pcg::JUMP)
+    }
+
+    /// Computes the number of steps separating this generator from `other`.
+    ///
+    /// Returns `Some(n)` where advancing `self` by `n` steps yields the state of `other`, or
+    /// `None` if the generators are on different streams (their increments differ). This is
+    /// useful for verifying that two substreams of a single stream haven't overlapped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let a = Pcg::new(42, 54);
+    /// let mut b = a.clone();
+    /// b.advance(1000);
+    /// assert_eq!(a.distance(&b), Some(1000));
+    /// ```
+    pub fn distance(&self, other: &Pcg<O>) -> Option<u64> {
+        if self.inc != other.inc {
+            return None;
+        }
+        Some(steps_between(self.state, other.state, self.inc))
+    }
+
+    /// Checks whether this generator and `other` would produce overlapping output if each took
+    /// `horizon` more steps.
+    ///
+    /// Generators on the same stream walk the same cycle of states, so they overlap when one is
+    /// less than `horizon` steps ahead of the other, as measured with `distance`. Generators on
+    /// different streams never produce the same sequence of states, so they never overlap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let a = Pcg::new(42, 54);
+    /// let b = a.clone_advance(1000);
+    /// assert!(a.overlaps_within(&b, 1001));
+    /// assert!(!a.overlaps_within(&b, 1000));
+    /// assert!(!a.overlaps_within(&Pcg::new(42, 55), u64::MAX));
+    /// ```
+    pub fn overlaps_within(&self, other: &Pcg<O>, horizon: u64) -> bool {
+        match self.distance(other) {
+            Some(ahead) => ahead < horizon || ahead.wrapping_neg() < horizon,
+            None => false,
+        }
+    }
+
+    /// Returns the number of steps the generator has taken since it was constructed or `reset`.
+    ///
+    /// As with `advance`, a step is a single `next_u32`, while each `next_u64` takes two steps
+    /// unless the permutation produces 64 bits. The position is measured from the state that
+    /// `reset` rewinds to, along the generator's current stream, and it wraps around after the
+    /// full period of `2^64` steps.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    /// use rand_core::RngCore;
+    ///
+    /// let mut rng = Pcg::new(42, 54);
+    /// rng.next_u32();
+    /// rng.next_u64();
+    /// assert_eq!(rng.position(), 3);
+    ///
+    /// rng.reset();
+    /// assert_eq!(rng.position(), 0);
+    /// ```
+    pub fn position(&self) -> u64 {
+        steps_between(self.init_state, self.state, self.inc)
+    }
+
+    /// Moves the generator to an absolute `position`, counted in steps from the state that
+    /// `reset` rewinds to, in `O(log position)` time.
+    ///
+    /// Positions before the current one rewind the generator, so handing out a fixed offset to
+    /// each of several workers gives them the same generators regardless of how far the shared
+    /// one has already advanced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    /// use rand_core::RngCore;
+    ///
+    /// let mut rng = Pcg::new(42, 54);
+    /// let first = rng.next_u32();
+    /// rng.advance_to(0);
+    /// assert_eq!(rng.next_u32(), first);
+    /// ```
+    pub fn advance_to(&mut self, position: u64) {
+        self.advance(position.wrapping_sub(self.position()));
+    }
+
+    /// Generates a uniformly distributed 32-bit value in the range `[0, bound)`.
+    ///
+    /// This is the same rejection sampling algorithm as the reference `pcg32_boundedrand_r`, which
+    /// avoids the bias introduced by simply taking a value modulo `bound`.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `bound` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut rng = Pcg::default();
+    /// let roll = rng.next_bounded_u32(6) + 1;
+    /// assert!(roll >= 1 && roll <= 6);
+    /// ```
+    pub fn next_bounded_u32(&mut self, bound: u32) -> u32 {
+        assert!(bound > 0, "bound must be greater than 0");
+
+        // Values below the threshold would make some outputs more likely than others, so they
+        // are rejected. The threshold is `2^32 % bound`, computed without 64-bit math.
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let r = self.next_u32();
+            if r >= threshold {
+                return r % bound;
+            }
+        }
+    }
+
+    /// Generates a uniformly distributed 64-bit value in the range `[0, bound)`.
+    ///
+    /// This is the 64-bit equivalent of `next_bounded_u32`.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `bound` is 0.
+    pub fn next_bounded_u64(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "bound must be greater than 0");
+
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let r = self.next_u64();
+            if r >= threshold {
+                return r % bound;
+            }
+        }
+    }
+
+    /// Discards values that were generated ahead of time, which no longer follow from the state
+    /// once the generator has been repositioned
+    fn discard_cached(&mut self) {
+        #[cfg(feature = "std")]
+        {
+            self.cached_normal = None;
+        }
+    }
+
+    /// Computes the affine map `x -> mult * x + plus` that moves a state forward by `delta`
+    /// steps, returned as `(mult, plus)`
+    fn jump(&self, delta: u64) -> (u64, u64) {
+        let mut acc_mult: u64 = 1;
+        let mut acc_plus: u64 = 0;
+        let mut cur_mult = INCREMENTOR;
+        let mut cur_plus = self.inc;
+        let mut delta = delta;
+
+        // Square-and-multiply over the affine map `x -> cur_mult * x + cur_plus`
+        while delta > 0 {
+            if delta & 1 == 1 {
+                acc_mult = acc_mult.wrapping_mul(cur_mult);
+                acc_plus = acc_plus.wrapping_mul(cur_mult).wrapping_add(cur_plus);
+            }
+            cur_plus = cur_mult.wrapping_add(1).wrapping_mul(cur_plus);
+            cur_mult = cur_mult.wrapping_mul(cur_mult);
+            delta >>= 1;
+        }
+        (acc_mult, acc_plus)
+    }
+
+    /// Records that `n` values were produced, when the `trace` feature is enabled
+    #[inline(always)]
+    fn record_generated(&mut self, n: u64) {
+        #[cfg(feature = "trace")]
+        {
+            self.count = self.count.wrapping_add(n);
+        }
+        #[cfg(not(feature = "trace"))]
+        let _ = n;
+    }
+
+    /// Applies the output permutation to the current state and advances the LCG by one step
+    fn next_permuted(&mut self) -> u64 {
+        let old_state = self.state;
+        self.step();
+        O::permute(old_state)
+    }
+
+    /// Advances the internal state of the LCG by a single step
+    const fn step(&mut self) {
+        debug_assert!(self.inc & 1 == 1, "the increment must be odd");
+        self.state = next_state(self.state, self.inc);
+    }
+}
+
+/// Steps a generator that's represented by its raw state and increment, without needing a `Pcg`.
+///
+/// Returns the XSH RR output for `state` along with the state that follows it, so callers can
+/// thread the state through explicitly. Iterating this produces the same values as calling
+/// `next_u32` on a `Pcg` with the default permutation, constructed with `Pcg::from_state(state,
+/// inc)`. As with `from_state`, `inc` should be odd for the generator to have a full period.
+///
+/// # Examples
+///
+/// ```
+/// use pcg::{pcg_next, Pcg};
+/// use rand_core::RngCore;
+///
+/// let mut rng = Pcg::new(42, 54);
+/// let (value, state) = pcg_next(rng.state(), rng.increment());
+/// assert_eq!(rng.next_u32(), value);
+/// assert_eq!(rng.state(), state);
+/// ```
+pub fn pcg_next(state: u64, inc: u64) -> (u32, u64) {
+    (output::xsh_rr(state), next_state(state, inc))
+}
+
+/// Computes the state that follows `state` in the LCG with increment `inc`
+const fn next_state(state: u64, inc: u64) -> u64 {
+    state.wrapping_mul(INCREMENTOR).wrapping_add(inc)
+}
+
+/// Recovers the seed that `Pcg::new` turns into `state` on the stream with increment `inc`
+fn unseed(state: u64, inc: u64) -> u64 {
+    // Undo the seeding procedure: `state = (inc + seed) * mult + inc`
+    state
+        .wrapping_sub(inc)
+        .wrapping_mul(INCREMENTOR_INVERSE)
+        .wrapping_sub(inc)
+}
+
+/// Computes the number of steps it takes to get from state `from` to state `to` in the LCG with
+/// increment `inc`
+fn steps_between(from: u64, to: u64, inc: u64) -> u64 {
+    let mut cur_state = from;
+    let mut cur_mult = INCREMENTOR;
+    let mut cur_plus = inc;
+    let mut the_bit: u64 = 1;
+    let mut distance: u64 = 0;
+
+    // Determine the distance one bit at a time, from the lowest bit up. Advancing by `2^k` steps
+    // only affects bits `k` and above of the state.
+    while cur_state != to {
+        if (cur_state & the_bit) != (to & the_bit) {
+            cur_state = cur_state.wrapping_mul(cur_mult).wrapping_add(cur_plus);
+            distance |= the_bit;
+        }
+        cur_plus = cur_mult.wrapping_add(1).wrapping_mul(cur_plus);
+        cur_mult = cur_mult.wrapping_mul(cur_mult);
+        the_bit <<= 1;
+    }
+    distance
+}
+
+impl Default for Pcg {
+    fn default() -> Self {
+        Pcg::DEFAULT
+    }
+}
+
+#[cfg(not(feature = "secure-debug"))]
+impl<O> fmt::Debug for Pcg<O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Pcg")
+            .field("state", &self.state)
+            .field("inc", &self.inc)
+            .finish()
+    }
+}
+
+/// Prints `Pcg { .. }` without any field values, so that the state can't leak into logs
+#[cfg(feature = "secure-debug")]
+impl<O> fmt::Debug for Pcg<O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Pcg").finish_non_exhaustive()
+    }
+}
+
+/// Compares the current state and increment, which determine every value produced by `RngCore`
+///
+/// The deviate cached by `next_normal` is not compared, so equal generators can return different
+/// values from their next `next_normal` call.
+impl<O> PartialEq for Pcg<O> {
+    fn eq(&self, other: &Pcg<O>) -> bool {
+        self.state == other.state && self.inc == other.inc
+    }
+}
+
+impl<O> Eq for Pcg<O> {}
+
+impl<O> Hash for Pcg<O> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.state.hash(state);
+        self.inc.hash(state);
+    }
+}
+
+impl<O: OutputFn> RngCore for Pcg<O> {
+    /// Generates a 32-bit value
+    ///
+    /// With the default XSH RR permutation this matches the reference `pcg32_random_r`: the
+    /// state is advanced once and the output is computed from the previous state. Permutations
+    /// that produce 64 bits return the high 32 bits of their output.
+    fn next_u32(&mut self) -> u32 {
+        self.record_generated(1);
+        let value = self.next_permuted();
+        if O::BITS == 64 {
+            (value >> 32) as u32
+        } else {
+            value as u32
+        }
+    }
+
+    /// Generates a 64-bit value
+    ///
+    /// Permutations that produce 32 bits are called twice: the first output makes up the high 32
+    /// bits and the second makes up the low 32 bits.
+    fn next_u64(&mut self) -> u64 {
+        self.record_generated(1);
+        if O::BITS == 64 {
+            self.next_permuted()
+        } else {
+            let high = self.next_permuted() as u32;
+            let low = self.next_permuted() as u32;
+            (u64::from(high) << 32) | u64::from(low)
+        }
+    }
+
+    /// Fills `dest` with bytes from successive `next_u64` values
+    ///
+    /// Each value is written in little-endian order, regardless of the platform's native byte
+    /// order. If the length of `dest` isn't a multiple of 8, the final value is truncated to the
+    /// bytes that are needed.
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let mut chunks = dest.chunks_exact_mut(8);
+        for chunk in &mut chunks {
+            chunk.copy_from_slice(&self.next_u64().to_le_bytes());
+        }
+
+        let tail = chunks.into_remainder();
+        if !tail.is_empty() {
+            let bytes = self.next_u64().to_le_bytes();
+            tail.copy_from_slice(&bytes[..tail.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// The number of 8-bit buckets that the seed of a `Pcg` is made of
+const SEED_LEN: usize = 8;
+
+/// A helper for converting seeds between their byte and integer forms
+///
+/// Generators are seeded with plain byte arrays, so this wrapper isn't needed to call
+/// `from_seed`, but the conversion traits defined for it make it easy to switch between a seed's
+/// bytes and the integer it represents. `N` is the length of the seed in bytes, and defaults to
+/// the 8 bytes of a `Pcg` seed. The bytes are interpreted in big-endian order, just like
+/// `from_seed`: the byte at index 0 is the most significant byte of the converted integer.
+///
+/// For example: `[0, 1, 2, 3, 4, 5, 6, 7]` corresponds to `0x0001020304050607` when converted to
+/// the packed unsigned integer representation.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "[u8; N]: Serialize",
+        deserialize = "[u8; N]: Deserialize<'de>"
+    ))
+)]
+pub struct PcgSeed<const N: usize = SEED_LEN>(pub [u8; N]);
+
+/// A seed for `Pcg` and `Pcg32`, which packs into a `u64`
+pub type Pcg32Seed = PcgSeed<8>;
+
+/// A seed for `Pcg64`, which packs into a `u128`
+pub type Pcg64Seed = PcgSeed<16>;
+
+/// A wrapper type for u64 so we can define methods on a built-in primitive
+///
+/// This enables, amongst other things, conversions from `u64` to `PcgSeed`.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct U64(pub u64);
+
+impl From<PcgSeed> for U64 {
+    fn from(seed: PcgSeed) -> Self {
+        // The 0th index of the array is the most significant byte, so the array is packed as a
+        // big-endian integer regardless of the platform's native byte order
+        U64(u64::from_be_bytes(seed.0))
+    }
+}
+
+impl<const N: usize> Default for PcgSeed<N> {
+    fn default() -> Self {
+        Self([0; N])
+    }
+}
+
+impl<const N: usize> AsMut<[u8]> for PcgSeed<N> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for PcgSeed<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for PcgSeed<N> {
+    fn from(bytes: [u8; N]) -> Self {
+        PcgSeed(bytes)
+    }
+}
+
+impl<const N: usize> From<PcgSeed<N>> for [u8; N] {
+    fn from(seed: PcgSeed<N>) -> Self {
+        seed.0
+    }
+}
+
+/// Exports the current state of a generator as a seed.
+///
+/// The seed is chosen so that `Pcg::from_seed` maps it back to the same state, which makes it
+/// possible to snapshot a generator and re-seed from it later. A seed only holds 8 bytes, so the
+/// stream is dropped: the restored generator is always on the default stream `from_seed` uses.
+/// Use `Pcg::to_bytes` and `Pcg::from_bytes` to keep the stream as well.
+///
+/// # Examples
+///
+/// ```
+/// use pcg::{Pcg, PcgSeed};
+/// use rand_core::{RngCore, SeedableRng};
+///
+/// let mut rng = Pcg::from_seed([0, 1, 2, 3, 4, 5, 6, 7]);
+/// rng.next_u32();
+/// let restored = Pcg::from_seed(PcgSeed::from(&rng).0);
+/// assert_eq!(restored, rng);
+/// ```
+impl<O: OutputFn> From<&Pcg<O>> for PcgSeed {
+    fn from(pcg: &Pcg<O>) -> Self {
+        PcgSeed::from(unseed(pcg.state, (INIT_INC << 1) | 1))
+    }
+}
+
+impl<const N: usize> TryFrom<&[u8]> for PcgSeed<N> {
+    type Error = TryFromSliceError;
+
+    /// Builds a seed from a byte slice, failing unless the slice is exactly `N` bytes long
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        <[u8; N]>::try_from(bytes).map(PcgSeed)
+    }
+}
+
+impl From<u64> for PcgSeed {
+    fn from(init: u64) -> Self {
+        PcgSeed(init.to_be_bytes())
+    }
+}
+
+impl From<U64> for PcgSeed {
+    fn from(init: U64) -> Self {
+        init.0.into()
+    }
+}
+
+impl From<u128> for Pcg64Seed {
+    fn from(init: u128) -> Self {
+        PcgSeed(init.to_be_bytes())
+    }
+}
+
+impl From<Pcg64Seed> for u128 {
+    fn from(seed: Pcg64Seed) -> Self {
+        u128::from_be_bytes(seed.0)
+    }
+}
+
+/// Constructs a generator from a `(seed, seq)` pair with `Pcg::new`.
+///
+/// Note that this isn't the inverse of converting a `Pcg` into a tuple, which gives the raw
+/// `(state, inc)` pair instead. Use `Pcg::from_state` to restore a generator from that.
+impl From<(u64, u64)> for Pcg {
+    fn from((seed, seq): (u64, u64)) -> Pcg {
+        Pcg::new(seed, seq)
+    }
+}
+
+/// Converts a generator into its raw `(state, inc)` pair.
+///
+/// Note that this isn't the inverse of converting a tuple into a `Pcg`, which treats the tuple as
+/// a `(seed, seq)` pair instead.
+///
+/// # Examples
+///
+/// ```
+/// use pcg::Pcg;
+///
+/// let rng = Pcg::from((42, 54));
+/// let (state, inc) = rng.clone().into();
+/// assert_eq!(Pcg::from_state(state, inc), rng);
+/// ```
+impl<O> From<Pcg<O>> for (u64, u64) {
+    fn from(pcg: Pcg<O>) -> (u64, u64) {
+        (pcg.state, pcg.inc)
+    }
+}
+
+impl SeedableRng for Pcg {
+    type Seed = [u8; SEED_LEN];
+
+    /// Creates a generator whose seed is the big-endian `u64` encoded by `seed`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    /// use rand_core::SeedableRng;
+    ///
+    /// let rng = Pcg::from_seed([0, 1, 2, 3, 4, 5, 6, 7]);
+    /// assert_eq!(rng, Pcg::from_seed(0x0001_0203_0405_0607u64.to_be_bytes()));
+    /// ```
+    fn from_seed(seed: Self::Seed) -> Pcg {
+        Pcg::new(u64::from_be_bytes(seed), INIT_INC)
+    }
+
+    /// Creates a generator from a `u64`, scrambling it first so that nearby values, such as 0, 1
+    /// and 2, produce unrelated streams of output
+    fn seed_from_u64(state: u64) -> Pcg {
+        Pcg::new(mix64(state), INIT_INC)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashSet;
+    use std::fmt::{Binary, Debug};
+
+    /// A bit mask for u8
+    const MASK: u8 = 0b11111111;
+
+    /// A helper function to compare variables of a type that can be represented as a binary string
+    ///
+    /// This provides more helpful/convenient error messages which will show the binary
+    /// representations of integers rather than the base 10 representations.
+    fn assert_eq_binary<T>(a: T, b: T)
+    where
+        T: PartialEq + Debug + Binary,
+    {
+        let b_str_a = format!("{:b}", a);
+        let b_str_b = format!("{:b}", b);
+        assert_eq!(b_str_a, b_str_b);
+    }
+
+    #[test]
+    fn test_seed_to_u64() {
+        let mut seed = PcgSeed::default();
+        seed.0[0] = MASK;
+
+        let converted_int = U64::from(seed);
+        let expected = (MASK as u64) << ((SEED_LEN - 1) * 8);
+        assert_eq_binary(converted_int.0, expected);
+
+        let mut seed = PcgSeed::default();
+        seed.0[SEED_LEN - 1] = MASK;
+        let converted_int = U64::from(seed);
+        let expected = MASK as u64;
+        assert_eq_binary(converted_int.0, expected)
+    }
+
+    #[test]
+    fn test_u64_to_seed() {
+        let integer = (MASK as u64) << ((SEED_LEN - 1) * 8);
+        let seed = PcgSeed::from(integer);
+        let mut expected = PcgSeed::default();
+        expected.0[0] = MASK;
+        assert_eq!(seed, expected);
+
+        let integer = MASK as u64;
+        let seed = PcgSeed::from(integer);
+        let mut expected = PcgSeed::default();
+        expected.0[SEED_LEN - 1] = MASK;
+        assert_eq!(seed, expected);
+    }
+
+    #[test]
+    fn test_next_u64_bit_balance() {
+        let mut rng = Pcg::default();
+        let samples = 10_000;
+        let mut counts = [0usize; 64];
+
+        for _ in 0..samples {
+            let value = rng.next_u64();
+            for (bit, count) in counts.iter_mut().enumerate() {
+                *count += ((value >> bit) & 1) as usize;
+            }
+        }
+
+        // Every bit, including the high bits, should be set roughly half of the time
+        for (bit, &count) in counts.iter().enumerate() {
+            let ratio = count as f64 / samples as f64;
+            assert!(
+                (0.45..0.55).contains(&ratio),
+                "bit {} was set in {} of samples",
+                bit,
+                ratio
+            );
+        }
+    }
+
+    #[test]
+    fn test_next_u64_serial_correlation() {
+        let mut rng = Pcg::new(42, 54);
+        let samples = 10_000;
+        let values: Vec<f64> = (0..samples)
+            .map(|_| rng.next_u64() as f64 / u64::MAX as f64)
+            .collect();
+        let mean = values.iter().sum::<f64>() / samples as f64;
+        let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>();
+        let covariance = values
+            .windows(2)
+            .map(|pair| (pair[0] - mean) * (pair[1] - mean))
+            .sum::<f64>();
+        let correlation = covariance / variance;
+        assert!(
+            correlation.abs() < 0.05,
+            "serial correlation was {}",
+            correlation
+        );
+    }
+
+    #[test]
+    fn test_next_u32_known_answer() {
+        let mut rng = Pcg::new(42, 54);
+
+        // Output of the reference `pcg32_srandom_r(&rng, 42, 54)` followed by `pcg32_random_r`
+        let expected: [u32; 8] = [
+            0xa15c_02b7,
+            0x7b47_f409,
+            0xba1d_3330,
+            0x83d2_f293,
+            0xbfa4_784b,
+            0xcbed_606e,
+            0xbfc6_a3ad,
+            0x812f_ff6d,
+        ];
+        for &value in expected.iter() {
+            assert_eq!(rng.next_u32(), value);
+        }
+    }
+
+    #[test]
+    fn test_next_u64_from_next_u32() {
+        let mut a = Pcg::new(42, 54);
+        let mut b = a.clone();
+        let high = u64::from(b.next_u32());
+        let low = u64::from(b.next_u32());
+        assert_eq!(a.next_u64(), (high << 32) | low);
+    }
+
+    #[test]
+    fn test_seed_byte_positions() {
+        assert_eq!(U64::from(PcgSeed([0, 0, 0, 0, 0, 0, 0, 1])), U64(1));
+        assert_eq!(
+            U64::from(PcgSeed([1, 0, 0, 0, 0, 0, 0, 0])),
+            U64(0x0100_0000_0000_0000)
+        );
+    }
+
+    #[test]
+    fn test_u64_seed_roundtrip_range() {
+        for x in (0..u64::MAX).step_by(0x0123_4567_89ab_cdef) {
+            assert_eq_binary(U64::from(PcgSeed::from(x)).0, x);
+        }
+    }
+
+    #[test]
+    fn test_u64_seed_roundtrip_edge_cases() {
+        let values = [
+            0,
+            1,
+            u64::MAX,
+            u64::MAX - 1,
+            0xaaaa_aaaa_aaaa_aaaa,
+            0x5555_5555_5555_5555,
+            0xff00_ff00_ff00_ff00,
+            0x00ff_00ff_00ff_00ff,
+            0x0123_4567_89ab_cdef,
+            1 << 63,
+        ];
+
+        for &x in values.iter() {
+            assert_eq_binary(U64::from(PcgSeed::from(x)).0, x);
+        }
+    }
+
+    #[test]
+    fn test_seed_is_big_endian() {
+        let seed = PcgSeed::from(0x0001_0203_0405_0607);
+        assert_eq!(seed, PcgSeed([0, 1, 2, 3, 4, 5, 6, 7]));
+    }
+
+    #[test]
+    fn test_advance_matches_stepping() {
+        for n in 0..64 {
+            let mut advanced = Pcg::new(42, 54);
+            let mut stepped = advanced.clone();
+            advanced.advance(n);
+            for _ in 0..n {
+                stepped.next_u32();
+            }
+            assert_eq!(advanced, stepped);
+
+            // `next_u64` consumes two steps per call
+            let mut advanced = Pcg::new(42, 54);
+            let mut stepped = advanced.clone();
+            advanced.advance(2 * n);
+            for _ in 0..n {
+                stepped.next_u64();
+            }
+            assert_eq!(advanced, stepped);
+        }
+    }
+
+    #[test]
+    fn test_advance_zero() {
+        let mut rng = Pcg::default();
+        rng.advance(0);
+        assert_eq!(rng, Pcg::default());
+    }
+
+    #[test]
+    fn test_backstep_restores_state() {
+        let mut rng = Pcg::new(42, 54);
+        let start = rng.clone();
+
+        rng.next_u32();
+        rng.backstep(1);
+        assert_eq!(rng, start);
+
+        rng.next_u64();
+        rng.backstep(2);
+        assert_eq!(rng, start);
+
+        rng.advance(u64::MAX);
+        rng.advance(1);
+        assert_eq!(rng, start);
+    }
+
+    #[test]
+    fn test_distance() {
+        let rng = Pcg::new(42, 54);
+        assert_eq!(rng.distance(&rng), Some(0));
+
+        for &k in [1, 2, 3, 1000, 1 << 40, u64::MAX].iter() {
+            let mut other = rng.clone();
+            other.advance(k);
+            assert_eq!(rng.distance(&other), Some(k));
+        }
+
+        let other_stream = Pcg::new(42, 55);
+        assert_eq!(rng.distance(&other_stream), None);
+    }
+
+    #[test]
+    fn test_from_state_reproduces_sequence() {
+        let mut rng = Pcg::new(42, 54);
+        rng.advance(17);
+        let mut restored = Pcg::from_state(rng.state(), rng.increment());
+
+        for _ in 0..100 {
+            assert_eq!(rng.next_u32(), restored.next_u32());
+        }
+    }
+
+    #[test]
+    fn test_from_state_forces_odd_increment() {
+        assert_eq!(Pcg::from_state(0, 2).increment(), 3);
+    }
+
+    #[test]
+    fn test_set_state() {
+        let mut rng = Pcg::new(42, 54);
+        let inc = rng.increment();
+        rng.set_state(12345);
+        assert_eq!(rng.state(), 12345);
+        assert_eq!(rng.increment(), inc);
+    }
+
+    #[test]
+    fn test_equal_generators_hash_equal() {
+        fn hash_of(rng: &Pcg) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            rng.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let mut a = Pcg::new(42, 54);
+        let mut b = Pcg::new(42, 54);
+        assert_eq!(a, b);
+        assert_eq!(hash_of(&a), hash_of(&b));
+
+        a.next_u32();
+        b.next_u32();
+        assert_eq!(hash_of(&a), hash_of(&b));
+    }
+
+    #[cfg(feature = "getrandom")]
+    #[test]
+    fn test_from_entropy() {
+        let mut a = Pcg::from_entropy();
+        let mut b = Pcg::from_entropy();
+        assert_eq!(a.increment() & 1, 1);
+        assert_ne!(a.next_u64(), b.next_u64());
+    }
+
+    #[test]
+    fn test_bounded_is_uniform() {
+        let mut rng = Pcg::new(42, 54);
+        let bound = 6;
+        let samples = 60_000;
+        let mut counts_u32 = [0usize; 6];
+        let mut counts_u64 = [0usize; 6];
+
+        for _ in 0..samples {
+            counts_u32[rng.next_bounded_u32(bound) as usize] += 1;
+            counts_u64[rng.next_bounded_u64(u64::from(bound)) as usize] += 1;
+        }
+
+        let expected = samples / bound as usize;
+        for &count in counts_u32.iter().chain(counts_u64.iter()) {
+            assert!(
+                (count as f64 - expected as f64).abs() < expected as f64 * 0.05,
+                "bucket count {} too far from {}",
+                count,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_bounded_one() {
+        let mut rng = Pcg::default();
+        assert_eq!(rng.next_bounded_u32(1), 0);
+        assert_eq!(rng.next_bounded_u64(1), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bounded_zero_panics() {
+        Pcg::default().next_bounded_u32(0);
+    }
+
+    #[test]
+    fn test_bytes_roundtrip() {
+        let rng = Pcg::new(42, 54);
+        let bytes = rng.to_bytes();
+        assert_eq!(&bytes[..8], &rng.state().to_be_bytes());
+        assert_eq!(&bytes[8..], &rng.increment().to_be_bytes());
+        assert_eq!(Pcg::from_bytes(bytes), rng);
+    }
+
+    #[test]
+    fn test_from_bytes_sequences_match() {
+        let bytes = Pcg::default().to_bytes();
+        let mut a = Pcg::from_bytes(bytes);
+        let mut b = Pcg::from_bytes(bytes);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_seed_serde_round_trip() {
+        let seed = PcgSeed([0, 1, 2, 3, 4, 5, 6, 7]);
+        let json = serde_json::to_string(&seed).unwrap();
+        assert_eq!(json, "[0,1,2,3,4,5,6,7]");
+        assert_eq!(serde_json::from_str::<PcgSeed>(&json).unwrap(), seed);
+
+        let bytes = bincode::serialize(&seed).unwrap();
+        assert_eq!(bytes, seed.0);
+        assert_eq!(bincode::deserialize::<PcgSeed>(&bytes).unwrap(), seed);
+
+        let wide = Pcg64Seed::from(0x0001_0203_0405_0607_0809_0a0b_0c0d_0e0f);
+        let json = serde_json::to_string(&wide).unwrap();
+        assert_eq!(json, "[0,1,2,3,4,5,6,7,8,9,10,11,12,13,14,15]");
+        assert_eq!(serde_json::from_str::<Pcg64Seed>(&json).unwrap(), wide);
+        let bytes = bincode::serialize(&wide).unwrap();
+        assert_eq!(bincode::deserialize::<Pcg64Seed>(&bytes).unwrap(), wide);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_u64_serde_round_trip() {
+        let value = U64(0x0001_0203_0405_0607);
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(json, "283686952306183");
+        assert_eq!(serde_json::from_str::<U64>(&json).unwrap(), value);
+
+        let bytes = bincode::serialize(&value).unwrap();
+        assert_eq!(bincode::deserialize::<U64>(&bytes).unwrap(), value);
+    }
+
+    #[test]
+    fn test_reset_replays_sequence() {
+        let mut rng = Pcg::new(42, 54);
+        let first: Vec<u64> = (0..100).map(|_| rng.next_u64()).collect();
+        rng.reset();
+        let second: Vec<u64> = (0..100).map(|_| rng.next_u64()).collect();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_reset_after_advance() {
+        let mut rng = Pcg::default();
+        rng.advance(1 << 40);
+        rng.next_u32();
+        rng.reset();
+        assert_eq!(rng, Pcg::default());
+    }
+
+    #[test]
+    fn test_equality_ignores_starting_point() {
+        let mut rng = Pcg::new(42, 54);
+        rng.advance(10);
+        assert_eq!(Pcg::from_state(rng.state(), rng.increment()), rng);
+    }
+
+    #[test]
+    fn test_seed_hash_without_alloc() {
+        /// A hasher that records the bytes it is fed into a fixed buffer, so hashing can be
+        /// checked without any heap allocation
+        struct ArrayHasher {
+            bytes: [u8; 32],
+            len: usize,
+        }
+
+        impl Hasher for ArrayHasher {
+            fn finish(&self) -> u64 {
+                self.len as u64
+            }
+
+            fn write(&mut self, bytes: &[u8]) {
+                self.bytes[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+                self.len += bytes.len();
+            }
+        }
+
+        let seed = PcgSeed([0, 1, 2, 3, 4, 5, 6, 7]);
+        let mut hasher = ArrayHasher {
+            bytes: [0; 32],
+            len: 0,
+        };
+        seed.hash(&mut hasher);
+
+        // The seed bytes are fed to the hasher directly, after a length prefix
+        assert_eq!(&hasher.bytes[hasher.len - SEED_LEN..hasher.len], &seed.0);
+
+        // The hash is unchanged from the previous implementation, which hashed a `Vec`
+        let mut a = DefaultHasher::new();
+        let mut b = DefaultHasher::new();
+        seed.hash(&mut a);
+        seed.0.to_vec().hash(&mut b);
+        assert_eq!(a.finish(), b.finish());
+    }
+
+    #[test]
+    fn test_seed_try_from_slice() {
+        let bytes = [0, 1, 2, 3, 4, 5, 6, 7, 8];
+        let seed = PcgSeed::try_from(&bytes[..SEED_LEN]).unwrap();
+        assert_eq!(seed, PcgSeed::from([0, 1, 2, 3, 4, 5, 6, 7]));
+        assert_eq!(seed.as_ref(), &bytes[..SEED_LEN]);
+
+        assert!(<PcgSeed>::try_from(&bytes[..]).is_err());
+        assert!(<PcgSeed>::try_from(&bytes[..SEED_LEN - 1]).is_err());
+        assert!(<PcgSeed>::try_from(&[][..]).is_err());
+    }
+
+    #[test]
+    fn test_streams() {
+        let mut first_outputs: Vec<u32> =
+            Pcg::streams(42, 8).map(|mut rng| rng.next_u32()).collect();
+        first_outputs.sort_unstable();
+        first_outputs.dedup();
+        assert_eq!(first_outputs.len(), 8);
+
+        let increments: Vec<u64> = Pcg::streams(42, 8).map(|rng| rng.increment()).collect();
+        for (i, &inc) in increments.iter().enumerate() {
+            assert_eq!(inc, ((i as u64) << 1) | 1);
+        }
+
+        assert_eq!(Pcg::streams(42, 1).next(), Some(Pcg::new(42, 0)));
+        assert_eq!(Pcg::streams(42, 0).count(), 0);
+    }
+
+    #[test]
+    fn test_split() {
+        let mut parent = Pcg::new(42, 54);
+        let a = parent.split();
+        let b = parent.split();
+        assert_ne!(a, b);
+        assert_ne!(a.increment(), b.increment());
+
+        let mut other_parent = Pcg::new(42, 54);
+        let splits: Vec<Pcg> = (0..2).map(|_| other_parent.split()).collect();
+        assert_eq!(splits, vec![a, b]);
+        assert_eq!(parent, other_parent);
+    }
+
+    #[test]
+    fn test_set_stream() {
+        let mut rng = Pcg::new(42, 54);
+        assert_eq!(rng.stream(), 54);
+
+        for &seq in [0, 1, 54, 12345, u64::MAX >> 1].iter() {
+            let state = rng.state();
+            rng.set_stream(seq);
+            assert_eq!(rng.stream(), seq);
+            assert_eq!(rng.state(), state);
+        }
+    }
+
+    #[test]
+    fn test_fill_bytes_known_answer() {
+        let mut rng = Pcg::new(42, 54);
+        let mut bytes = [0u8; 20];
+        rng.fill_bytes(&mut bytes);
+
+        let expected: [u8; 20] = [
+            0x09, 0xf4, 0x47, 0x7b, 0xb7, 0x02, 0x5c, 0xa1, 0x93, 0xf2, 0xd2, 0x83, 0x30, 0x33,
+            0x1d, 0xba, 0x6e, 0x60, 0xed, 0xcb,
+        ];
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn test_fill_bytes_uses_little_endian_words() {
+        let mut a = Pcg::new(42, 54);
+        let mut b = a.clone();
+        let mut bytes = [0u8; 16];
+        a.fill_bytes(&mut bytes);
+        assert_eq!(&bytes[..8], &b.next_u64().to_le_bytes());
+        assert_eq!(&bytes[8..], &b.next_u64().to_le_bytes());
+    }
+
+    #[test]
+    fn test_fill_bytes_is_platform_independent() {
+        let mut rng = Pcg::new(42, 54);
+        let mut bytes = [0u8; 32];
+        rng.fill_bytes(&mut bytes);
+
+        let expected: [u8; 32] = [
+            0x09, 0xf4, 0x47, 0x7b, 0xb7, 0x02, 0x5c, 0xa1, 0x93, 0xf2, 0xd2, 0x83, 0x30, 0x33,
+            0x1d, 0xba, 0x6e, 0x60, 0xed, 0xcb, 0x4b, 0x78, 0xa4, 0xbf, 0x6d, 0xff, 0x2f, 0x81,
+            0xad, 0xa3, 0xc6, 0xbf,
+        ];
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn test_const_construction() {
+        const RNG: Pcg = Pcg::new(42, 54);
+        const DEFAULT: Pcg = Pcg::DEFAULT;
+        assert_eq!(RNG, Pcg::new(42, 54));
+        assert_eq!(DEFAULT, Pcg::default());
+    }
+
+    #[test]
+    #[cfg(feature = "trace")]
+    fn test_generated_count() {
+        let mut rng = Pcg::new(42, 54);
+        assert_eq!(rng.generated_count(), 0);
+
+        rng.next_u32();
+        rng.next_u64();
+        assert_eq!(rng.generated_count(), 2);
+
+        // 20 bytes take two whole values and one truncated value
+        let mut bytes = [0u8; 20];
+        rng.fill_bytes(&mut bytes);
+        assert_eq!(rng.generated_count(), 5);
+
+        let mut values = [0u32; 7];
+        rng.fill_u32(&mut values);
+        assert_eq!(rng.generated_count(), 12);
+    }
+
+    #[test]
+    fn test_peek() {
+        let mut rng = Pcg::new(42, 54);
+        assert_eq!(rng.peek_u64(), rng.peek_u64());
+        let peeked = rng.peek_u64();
+        assert_eq!(rng.next_u64(), peeked);
+
+        assert_eq!(rng.peek_u32(), rng.peek_u32());
+        let peeked = rng.peek_u32();
+        assert_eq!(rng.next_u32(), peeked);
+    }
+
+    #[test]
+    fn test_seed_from_u64_decorrelated() {
+        let outputs: Vec<u64> = (0..3)
+            .map(|seed| Pcg::seed_from_u64(seed).next_u64())
+            .collect();
+        for (i, a) in outputs.iter().enumerate() {
+            for b in outputs[i + 1..].iter() {
+                // Unrelated values differ in about half of their bits
+                let differing = (a ^ b).count_ones();
+                assert!((16..=48).contains(&differing), "{:#x} {:#x}", a, b);
+            }
+        }
+        assert_eq!(Pcg::seed_from_u64(1), Pcg::seed_from_u64(1));
+    }
+
+    #[test]
+    fn test_from_seed_known_answer() {
+        let mut rng = Pcg::from_seed([0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(rng, Pcg::new(0x0001_0203_0405_0607, INIT_INC));
+
+        let expected: [u32; 4] = [0x8c90_841a, 0x0919_98e1, 0x7856_206d, 0x0211_0280];
+        for &value in expected.iter() {
+            assert_eq!(rng.next_u32(), value);
+        }
+    }
+
+    #[test]
+    fn test_from_seed_matches_pcg_seed() {
+        let seed = PcgSeed::from(0xdead_beef_u64);
+        assert_eq!(Pcg::from_seed(seed.into()), Pcg::from_seed(seed.0));
+        assert_eq!(
+            Pcg::from_seed(seed.0),
+            Pcg::new(U64::from(seed).0, INIT_INC)
+        );
+    }
+
+    #[test]
+    fn test_from_rng() {
+        let mut source = Pcg::new(42, 54);
+        let mut expected = source.clone();
+        let rng = Pcg::from_rng(&mut source);
+
+        assert_eq!(rng.state(), expected.next_u64().swap_bytes());
+        assert_eq!(rng.increment(), expected.next_u64().swap_bytes() | 1);
+        assert_eq!(source, expected);
+        assert_ne!(Pcg::from_rng(&mut source), rng);
+    }
+
+    #[test]
+    fn test_period() {
+        const _: () = assert!(Pcg::PERIOD_LOG2 == 64);
+        assert_eq!(Pcg::default().period_log2(), 64);
+        assert_eq!(
+            Pcg::default().with_output::<output::RxsMXs>().period_log2(),
+            64
+        );
+    }
+
+    #[test]
+    fn test_pcg_next_matches_next_u32() {
+        let mut rng = Pcg::new(42, 54);
+        let (mut state, inc) = (rng.state(), rng.increment());
+        for _ in 0..100 {
+            let (value, next) = pcg_next(state, inc);
+            assert_eq!(value, rng.next_u32());
+            state = next;
+        }
+        assert_eq!(state, rng.state());
+    }
+
+    #[test]
+    fn test_jumped() {
+        let rng = Pcg::new(42, 54);
+        let original = rng.clone();
+        assert_ne!(rng.jumped(), rng);
+        assert_eq!(rng, original);
+
+        let mut chained = rng.clone();
+        for _ in 0..5 {
+            chained = chained.jumped();
+        }
+        let mut advanced = rng.clone();
+        advanced.advance(5 * Pcg::JUMP);
+        assert_eq!(chained, advanced);
+        assert_eq!(rng.distance(&chained), Some(5 << 32));
+    }
+
+    #[test]
+    fn test_seed_from_pcg_round_trip() {
+        let mut rng = Pcg::from_seed([0, 1, 2, 3, 4, 5, 6, 7]);
+        for _ in 0..10 {
+            let restored = Pcg::from_seed(PcgSeed::from(&rng).0);
+            assert_eq!(restored.state(), rng.state());
+            assert_eq!(restored, rng);
+            rng.next_u64();
+        }
+
+        // Only the state survives, so a generator on another stream comes back on the default one
+        let other = Pcg::new(42, 54);
+        let restored = Pcg::from_seed(PcgSeed::from(&other).0);
+        assert_eq!(restored.state(), other.state());
+        assert_ne!(restored.increment(), other.increment());
+    }
+
+    #[test]
+    fn test_clone_advance() {
+        let rng = Pcg::new(42, 54);
+        let original = rng.clone();
+        for n in 0..10 {
+            let mut by_u32 = rng.clone();
+            let mut by_u64 = rng.clone();
+            for _ in 0..n {
+                by_u32.next_u32();
+                by_u64.next_u64();
+            }
+            assert_eq!(rng.clone_advance(n), by_u32);
+            assert_eq!(rng.clone_advance(2 * n), by_u64);
+
+            // A 64-bit permutation takes a single step per `next_u64`
+            let wide = rng.clone().with_output::<output::RxsMXs>();
+            let mut by_wide = wide.clone();
+            for _ in 0..n {
+                by_wide.next_u64();
+            }
+            assert_eq!(wide.clone_advance(n), by_wide);
+        }
+        assert_eq!(rng, original);
+    }
+
+    #[cfg(feature = "secure-debug")]
+    #[test]
+    fn test_debug_is_redacted() {
+        let rng = Pcg::new(42, 54);
+        let debug = format!("{:?}", rng);
+        assert_eq!(debug, "Pcg { .. }");
+        assert!(!debug.contains(&format!("{:x}", rng.state())));
+        assert!(!debug.contains(&rng.state().to_string()));
+
+        let wide = Pcg64::new(42, 54);
+        assert_eq!(format!("{:?}", wide), "Pcg64 { .. }");
+        let fast = PcgMcg::new(42);
+        assert_eq!(format!("{:?}", fast), "PcgMcg { .. }");
+        let extended = PcgExt::<4>::new(42, 54);
+        let debug = format!("{:?}", extended);
+        assert_eq!(debug, "PcgExt { .. }");
+        assert!(!debug.contains(&extended.extension()[0].to_string()));
+    }
+
+    #[test]
+    fn test_fill_bytes_tail_is_prefix() {
+        let mut full = [0u8; 16];
+        Pcg::new(42, 54).fill_bytes(&mut full);
+
+        for &len in [0, 1, 7, 8, 9, 15].iter() {
+            let mut rng = Pcg::new(42, 54);
+            let mut bytes = [0u8; 16];
+            rng.fill_bytes(&mut bytes[..len]);
+            assert_eq!(&bytes[..len], &full[..len], "length {}", len);
+            assert!(bytes[len..].iter().all(|&b| b == 0));
+
+            // A partial word still consumes the whole value
+            let mut expected = Pcg::new(42, 54);
+            expected.advance(2 * (len as u64).div_ceil(8));
+            assert_eq!(rng, expected);
+        }
+    }
+
+    /// Computes the chi-square statistic of the pairs of lowest bits of consecutive values
+    fn low_bit_pair_chi_square<F: FnMut() -> u32>(mut next: F) -> f64 {
+        let samples = 10_000;
+        let mut counts = [0usize; 4];
+        let mut prev = next() & 1;
+        for _ in 0..samples {
+            let bit = next() & 1;
+            counts[(prev << 1 | bit) as usize] += 1;
+            prev = bit;
+        }
+        let expected = samples as f64 / 4.0;
+        counts
+            .iter()
+            .map(|&c| (c as f64 - expected).powi(2) / expected)
+            .sum()
+    }
+
+    #[test]
+    fn test_next_u32_low_bits_are_not_truncated_state() {
+        // The critical value of the chi-square distribution with 3 degrees of freedom at 0.1%
+        let critical = 16.27;
+
+        let mut rng = Pcg::new(42, 54);
+        assert!(low_bit_pair_chi_square(|| rng.next_u32()) < critical);
+        let mut wide = Pcg::new(42, 54).with_output::<output::RxsMXs>();
+        assert!(low_bit_pair_chi_square(|| wide.next_u32()) < critical);
+
+        // The lowest bit of the LCG state just alternates, so truncating it would fail badly
+        let mut raw = Pcg::new(42, 54);
+        let truncated = low_bit_pair_chi_square(|| {
+            raw.step();
+            raw.state() as u32
+        });
+        assert!(truncated > 1000.0);
+    }
+
+    #[test]
+    fn test_tuple_conversions() {
+        let rng = Pcg::from((42, 54));
+        assert_eq!(rng, Pcg::new(42, 54));
+        assert_eq!(rng.stream(), 54);
+
+        let (state, inc): (u64, u64) = rng.clone().into();
+        assert_eq!((state, inc), (rng.state(), rng.increment()));
+        assert_eq!(Pcg::from_state(state, inc), rng);
+
+        // The two tuples mean different things, so converting back doesn't round-trip
+        assert_ne!(Pcg::from((state, inc)), rng);
+    }
+
+    #[test]
+    fn test_discard() {
+        let rng = Pcg::new(42, 54);
+        for n in 0..10 {
+            let mut discarded = rng.clone();
+            discarded.discard(n);
+            let mut stepped = rng.clone();
+            for _ in 0..n {
+                stepped.next_u32();
+            }
+            assert_eq!(discarded, stepped);
+
+            let mut wide = rng.clone().with_output::<output::RxsMXs>();
+            let mut wide_stepped = wide.clone();
+            wide.discard(n);
+            for _ in 0..n {
+                wide_stepped.next_u64();
+            }
+            assert_eq!(wide, wide_stepped);
+        }
+    }
+
+    #[test]
+    fn test_position() {
+        let mut rng = Pcg::new(42, 54);
+        assert_eq!(rng.position(), 0);
+        for n in 1..10 {
+            rng.next_u64();
+            assert_eq!(rng.position(), 2 * n);
+        }
+        rng.reset();
+        assert_eq!(rng.position(), 0);
+
+        let mut wide = Pcg::new(42, 54).with_output::<output::RxsMXs>();
+        for _ in 0..7 {
+            wide.next_u64();
+        }
+        assert_eq!(wide.position(), 7);
+        wide.backstep(2);
+        assert_eq!(wide.position(), 5);
+        wide.reset();
+        assert_eq!(wide.position(), 0);
+    }
+
+    #[test]
+    fn test_advance_to() {
+        for k in 0..10 {
+            let mut fresh = Pcg::new(42, 54);
+            let mut expected = 0;
+            for _ in 0..=k {
+                expected = fresh.next_u64();
+            }
+
+            // Each `next_u64` takes two steps
+            let mut forward = Pcg::new(42, 54);
+            forward.advance_to(2 * k);
+            assert_eq!(forward.next_u64(), expected);
+
+            // Positions behind the current one rewind the generator
+            let mut rewound = Pcg::new(42, 54);
+            rewound.advance(100);
+            rewound.advance_to(2 * k);
+            assert_eq!(rewound.next_u64(), expected);
+            assert_eq!(rewound, fresh);
+        }
+
+        let mut wide = Pcg::new(42, 54).with_output::<output::RxsMXs>();
+        let mut fresh = wide.clone();
+        wide.advance_to(4);
+        for _ in 0..4 {
+            fresh.next_u64();
+        }
+        assert_eq!(wide.next_u64(), fresh.next_u64());
+    }
+
+    #[test]
+    fn test_seed_aliases() {
+        let seed: Pcg32Seed = PcgSeed::from(0x0001_0203_0405_0607);
+        assert_eq!(seed, PcgSeed::<8>([0, 1, 2, 3, 4, 5, 6, 7]));
+        assert_eq!(
+            Pcg::from_seed(seed.0),
+            Pcg::from_seed(PcgSeed::from(seed.0).into())
+        );
+        assert_eq!(Pcg64Seed::default().0, [0; 16]);
+    }
+
+    #[test]
+    fn test_u128_seed() {
+        let bytes = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+        let seed = Pcg64Seed::from(0x0001_0203_0405_0607_0809_0a0b_0c0d_0e0f);
+        assert_eq!(seed, PcgSeed(bytes));
+        assert_eq!(u128::from(seed), 0x0001_0203_0405_0607_0809_0a0b_0c0d_0e0f);
+        assert_eq!(Pcg64::from_seed(seed.0), Pcg64::from_seed(bytes));
+
+        let mut seed = Pcg64Seed::default();
+        seed.as_mut()[0] = MASK;
+        assert_eq_binary(u128::from(seed), (MASK as u128) << 120);
+
+        assert_eq!(Pcg64Seed::try_from(&bytes[..]).unwrap(), PcgSeed(bytes));
+        assert!(Pcg64Seed::try_from(&bytes[..SEED_LEN]).is_err());
+    }
+
+    #[test]
+    fn test_into_stream_iter() {
+        let mut rng = Pcg::new(42, 54);
+        rng.advance(1000);
+        let generators: Vec<Pcg> = rng.into_stream_iter().take(8).collect();
+        for (seq, generator) in generators.iter().enumerate() {
+            assert_eq!(*generator, Pcg::new(42, seq as u64));
+        }
+
+        let increments: HashSet<u64> = generators.iter().map(Pcg::increment).collect();
+        assert_eq!(increments.len(), generators.len());
+
+        let again: Vec<Pcg> = Pcg::new(42, 7).into_stream_iter().take(8).collect();
+        assert_eq!(again, generators);
+    }
+
+    #[test]
+    fn test_overlaps_within() {
+        let rng = Pcg::new(42, 54);
+
+        // Same stream, close together, in either order
+        let close = rng.clone_advance(100);
+        assert!(rng.overlaps_within(&close, 101));
+        assert!(close.overlaps_within(&rng, 101));
+        assert!(rng.overlaps_within(&rng, 1));
+        assert!(!rng.overlaps_within(&rng, 0));
+
+        // Same stream, far apart
+        let far = rng.jumped();
+        assert!(!rng.overlaps_within(&far, 1 << 20));
+        assert!(!far.overlaps_within(&rng, 1 << 20));
+        assert!(rng.overlaps_within(&far, (1 << 32) + 1));
+
+        // Different streams
+        let other = Pcg::new(42, 55);
+        assert!(!rng.overlaps_within(&other, u64::MAX));
     }
 }
@@ -0,0 +1,167 @@
+//! A PCG generator built on a multiplicative congruential generator, with no increment.
+
+use crate::consts::{INCREMENTOR, INIT_SEED};
+use crate::output::xsh_rs;
+use rand_core::{impls, Error, RngCore, SeedableRng};
+
+#[cfg(feature = "std")]
+use std::fmt;
+
+#[cfg(not(feature = "std"))]
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The `PcgMcg` struct is a faster PCG variant whose state is advanced by a multiplicative
+/// congruential generator.
+///
+/// Each step only multiplies the state, saving the addition of an increment that `Pcg` performs,
+/// and the output uses the cheaper XSH RS permutation. It matches the reference `pcg32_fast`
+/// (`pcg_mcg_64_xsh_rs_32`) generator. The tradeoff is that there are no streams, and the period
+/// is only `2^62` rather than `2^64`. The state must be odd to reach that period, so the lowest
+/// bit of the seed is forced to 1.
+#[derive(Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PcgMcg {
+    state: u64,
+}
+
+impl PcgMcg {
+    /// The base-2 logarithm of the generator's period
+    pub const PERIOD_LOG2: u32 = 62;
+
+    /// Constructs a new generator from a seed, following the seeding procedure of the reference
+    /// `pcg32_fast_srandom_r`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::PcgMcg;
+    /// use rand_core::RngCore;
+    ///
+    /// let mut rng = PcgMcg::new(42);
+    /// let value = rng.next_u32();
+    /// ```
+    pub const fn new(seed: u64) -> PcgMcg {
+        PcgMcg { state: seed | 1 }
+    }
+
+    /// Returns the current internal state of the generator
+    pub fn state(&self) -> u64 {
+        self.state
+    }
+}
+
+impl Default for PcgMcg {
+    fn default() -> Self {
+        PcgMcg::new(INIT_SEED)
+    }
+}
+
+#[cfg(not(feature = "secure-debug"))]
+impl fmt::Debug for PcgMcg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PcgMcg")
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+/// Prints `PcgMcg { .. }` without any field values, so that the state can't leak into logs
+#[cfg(feature = "secure-debug")]
+impl fmt::Debug for PcgMcg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PcgMcg").finish_non_exhaustive()
+    }
+}
+
+impl RngCore for PcgMcg {
+    /// Generates a 32-bit value
+    ///
+    /// As with `Pcg`, the state is advanced once and the output is computed from the previous
+    /// state.
+    fn next_u32(&mut self) -> u32 {
+        let old_state = self.state;
+        self.state = old_state.wrapping_mul(INCREMENTOR);
+        xsh_rs(old_state)
+    }
+
+    /// Generates a 64-bit value from two 32-bit values, high half first
+    fn next_u64(&mut self) -> u64 {
+        let high = u64::from(self.next_u32());
+        let low = u64::from(self.next_u32());
+        (high << 32) | low
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl SeedableRng for PcgMcg {
+    /// The seed is interpreted as a big-endian `u64`
+    type Seed = [u8; 8];
+
+    fn from_seed(seed: Self::Seed) -> PcgMcg {
+        PcgMcg::new(u64::from_be_bytes(seed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_answer() {
+        // The outputs of the reference `pcg32_fast` after `pcg32_fast_srandom_r(42)`
+        let mut rng = PcgMcg::new(42);
+        let expected: [u32; 6] = [
+            0x0000_0000,
+            0x5c40_0ccc,
+            0x03a8_459e,
+            0x9bdb_59c5,
+            0xf1c9_dcf5,
+            0xaac0_af3b,
+        ];
+        for &value in expected.iter() {
+            assert_eq!(rng.next_u32(), value);
+        }
+
+        let mut rng = PcgMcg::default();
+        let expected: [u32; 6] = [
+            0xafef_3262,
+            0xa0de_5c1b,
+            0x158c_cfdc,
+            0xea01_e00f,
+            0xf05b_36c4,
+            0x56c3_2941,
+        ];
+        for &value in expected.iter() {
+            assert_eq!(rng.next_u32(), value);
+        }
+    }
+
+    #[test]
+    fn test_state_stays_odd() {
+        let mut rng = PcgMcg::new(0);
+        assert_eq!(rng.state(), 1);
+        for _ in 0..1000 {
+            rng.next_u32();
+            assert_eq!(rng.state() & 1, 1);
+        }
+    }
+
+    #[test]
+    fn test_from_seed() {
+        assert_eq!(
+            PcgMcg::from_seed([0, 0, 0, 0, 0, 0, 0, 42]),
+            PcgMcg::new(42)
+        );
+    }
+}
@@ -0,0 +1,261 @@
+//! Hashing helpers for deriving generator state from other data.
+
+use crate::consts::{
+    FNV_OFFSET_BASIS, FNV_PRIME, GOLDEN_GAMMA, MIX_MULTIPLIER_1, MIX_MULTIPLIER_2,
+};
+use crate::{OutputFn, Pcg};
+
+#[cfg(feature = "std")]
+use std::hash::{Hash, Hasher};
+
+#[cfg(not(feature = "std"))]
+use core::hash::{Hash, Hasher};
+
+/// Scrambles a 64-bit value with the SplitMix64 output function.
+///
+/// Every bit of the input affects every bit of the output, so nearby inputs produce unrelated
+/// outputs.
+pub(crate) fn mix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(GOLDEN_GAMMA);
+    z = (z ^ (z >> 30)).wrapping_mul(MIX_MULTIPLIER_1);
+    z = (z ^ (z >> 27)).wrapping_mul(MIX_MULTIPLIER_2);
+    z ^ (z >> 31)
+}
+
+/// The 64-bit FNV-1a hash, which has no keys, so its output never changes between runs
+///
+/// Integers are hashed as their little-endian bytes, and `usize` as a `u64`, so the hash of a
+/// value is also the same on every platform. The default `Hasher` methods use the native byte
+/// order and width instead.
+struct Fnv1a(u64);
+
+impl Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes.iter() {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn write_u8(&mut self, value: u8) {
+        self.write(&[value]);
+    }
+
+    fn write_u16(&mut self, value: u16) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, value: u128) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64);
+    }
+}
+
+impl Pcg {
+    /// Constructs a generator whose seed and stream are derived from the hash of `value`.
+    ///
+    /// The value is hashed with FNV-1a, which unlike the randomly keyed hasher behind `HashMap`
+    /// has no keys, so the same value always gives the same generator, in every run. This makes
+    /// it easy to give each entity its own reproducible generator. Integers are hashed as their
+    /// little-endian bytes, and `usize` and `isize` as 64-bit values, so the generator is also the
+    /// same on every platform, whatever its byte order or pointer width. Note that the stability
+    /// only extends as far as the value's `Hash` impl, which is free to hash different data on
+    /// different platforms or in different versions of the crate that defines it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let user_id = "user-1234";
+    /// assert_eq!(Pcg::seed_from_hashable(&user_id), Pcg::seed_from_hashable(&user_id));
+    /// ```
+    pub fn seed_from_hashable<H: Hash + ?Sized>(value: &H) -> Pcg {
+        let mut hasher = Fnv1a(FNV_OFFSET_BASIS);
+        value.hash(&mut hasher);
+        let seed = mix64(hasher.finish());
+        Pcg::new(seed, mix64(seed))
+    }
+}
+
+impl<O: OutputFn> Pcg<O> {
+    /// Stirs additional entropy into the generator.
+    ///
+    /// The bytes are hashed together with the current state to produce a new state, while the
+    /// stream is left unchanged. Mixing the same bytes into the same state always yields the
+    /// same result.
+    ///
+    /// This is not a cryptographic reseed: PCG is not a cryptographically secure generator, and
+    /// anyone who observes enough output can still recover the state.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut rng = Pcg::new(42, 54);
+    /// rng.mix_entropy(b"some fresh entropy");
+    /// assert_eq!(rng.stream(), 54);
+    /// ```
+    pub fn mix_entropy(&mut self, bytes: &[u8]) {
+        let mut hash = self.state;
+        for chunk in bytes.chunks(8) {
+            let mut word = [0u8; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            hash = mix64(hash ^ u64::from_le_bytes(word));
+        }
+        // Fold in the length so that trailing zero bytes aren't ignored
+        self.state = mix64(hash ^ bytes.len() as u64);
+        self.discard_cached();
+    }
+
+    /// Derives a child generator from this one, keyed by `label`.
+    ///
+    /// The child's seed and stream are hashed from the parent's current state and the label, so
+    /// a parent in a given state always produces the same child for a given label, and different
+    /// labels produce unrelated children. Unlike `split`, which draws two values from the parent,
+    /// the parent is only advanced by a single step, so that forking again yields new children.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut a = Pcg::new(42, 54);
+    /// let mut b = a.clone();
+    /// assert_eq!(a.fork(7), b.fork(7));
+    /// ```
+    pub fn fork(&mut self, label: u64) -> Pcg<O> {
+        let key = mix64(label);
+        let seed = mix64(self.state ^ key);
+        let seq = mix64(seed.wrapping_add(key));
+        self.step();
+        Pcg::new(seed, seq).with_output()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::RngCore;
+
+    #[test]
+    fn test_mix_entropy_diverges() {
+        let mut a = Pcg::new(42, 54);
+        let mut b = a.clone();
+        a.mix_entropy(b"first");
+        b.mix_entropy(b"second");
+        assert_ne!(a.next_u64(), b.next_u64());
+        assert_eq!(a.increment(), b.increment());
+    }
+
+    #[test]
+    fn test_fork_labels_differ() {
+        let parent = Pcg::new(42, 54);
+        let mut first = parent.clone().fork(1);
+        let mut second = parent.clone().fork(2);
+        assert_ne!(first, second);
+        assert_ne!(first.stream(), second.stream());
+        assert_ne!(first.next_u64(), second.next_u64());
+    }
+
+    #[test]
+    fn test_fork_deterministic() {
+        let mut a = Pcg::new(42, 54);
+        let mut b = a.clone();
+        assert_eq!(a.fork(3), b.fork(3));
+        assert_eq!(a, b);
+
+        // The parent moves on, so forking again with the same label gives a new child
+        let mut c = Pcg::new(42, 54);
+        let child = c.fork(3);
+        assert_ne!(c.fork(3), child);
+    }
+
+    #[test]
+    fn test_fnv1a_known_answer() {
+        let mut hasher = Fnv1a(FNV_OFFSET_BASIS);
+        hasher.write(b"a");
+        assert_eq!(hasher.finish(), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn test_fnv1a_integers_are_little_endian() {
+        let mut integer = Fnv1a(FNV_OFFSET_BASIS);
+        integer.write_u32(0x0403_0201);
+        let mut bytes = Fnv1a(FNV_OFFSET_BASIS);
+        bytes.write(&[1, 2, 3, 4]);
+        assert_eq!(integer.finish(), bytes.finish());
+
+        let mut size = Fnv1a(FNV_OFFSET_BASIS);
+        size.write_usize(7);
+        let mut wide = Fnv1a(FNV_OFFSET_BASIS);
+        wide.write_u64(7);
+        assert_eq!(size.finish(), wide.finish());
+    }
+
+    #[test]
+    fn test_seed_from_hashable_known_answer() {
+        // Computed independently from the little-endian bytes of each value
+        let mut rng = Pcg::seed_from_hashable(&0x0123_4567_89ab_cdef_u64);
+        assert_eq!(rng.state(), 0xe82e_036a_5c65_31dd);
+        assert_eq!(rng.increment(), 0xe0b8_5340_550b_680f);
+        assert_eq!(rng.next_u32(), 0x2e39_61d8);
+
+        // `str` hashes its bytes followed by a `0xff` terminator
+        let rng = Pcg::seed_from_hashable("user-1234");
+        assert_eq!(rng.state(), 0x81cb_987d_fd2c_fa2f);
+        assert_eq!(rng.increment(), 0xff04_a23e_d0d7_81f1);
+    }
+
+    #[test]
+    fn test_seed_from_hashable() {
+        assert_eq!(
+            Pcg::seed_from_hashable(&42u64),
+            Pcg::seed_from_hashable(&42u64)
+        );
+        assert_eq!(
+            Pcg::seed_from_hashable("abc"),
+            Pcg::seed_from_hashable("abc")
+        );
+        assert_ne!(
+            Pcg::seed_from_hashable(&42u64),
+            Pcg::seed_from_hashable(&43u64)
+        );
+        assert_ne!(
+            Pcg::seed_from_hashable("abc"),
+            Pcg::seed_from_hashable("abd")
+        );
+        assert_ne!(
+            Pcg::seed_from_hashable(&1u64).stream(),
+            Pcg::seed_from_hashable(&2u64).stream()
+        );
+    }
+
+    #[test]
+    fn test_mix_entropy_deterministic() {
+        let mut a = Pcg::new(42, 54);
+        let mut b = a.clone();
+        a.mix_entropy(&[1, 2, 3]);
+        b.mix_entropy(&[1, 2, 3]);
+        assert_eq!(a, b);
+
+        let mut c = Pcg::new(42, 54);
+        c.mix_entropy(&[1, 2, 3, 0]);
+        assert_ne!(a, c);
+    }
+}
@@ -0,0 +1,345 @@
+//! Output permutations that turn the internal LCG state into random values.
+//!
+//! PCG generators advance a plain LCG and then scramble the state with one of a family of
+//! permutations. The functions in this module take the state that is being permuted and return
+//! the output value.
+
+use crate::consts::{RXS_M_XS_INVERSE, RXS_M_XS_MULTIPLIER};
+use crate::Pcg;
+use rand_core::{Error, RngCore};
+
+#[cfg(feature = "std")]
+use std::fmt;
+
+#[cfg(not(feature = "std"))]
+use core::fmt;
+
+/// The XSH RR permutation: an xorshift of the high bits followed by a random rotation
+///
+/// This produces 32 bits of output from 64 bits of state, and is the permutation used by `Pcg`.
+pub fn xsh_rr(state: u64) -> u32 {
+    let xor_shifted = (((state >> 18) ^ state) >> 27) as u32;
+    let rot = (state >> 59) as u32;
+    xor_shifted.rotate_right(rot)
+}
+
+/// The XSH RS permutation: an xorshift of the high bits followed by a random shift
+///
+/// This produces 32 bits of output from 64 bits of state.
+pub fn xsh_rs(state: u64) -> u32 {
+    (((state >> 22) ^ state) >> ((state >> 61) + 22)) as u32
+}
+
+/// The RXS M XS permutation: a random xorshift, a multiply, and a fixed xorshift
+///
+/// This produces 64 bits of output from 64 bits of state. Unlike XSH RR, it is a bijection, so it
+/// can be undone with `inverse_rxs_m_xs`.
+pub fn rxs_m_xs(state: u64) -> u64 {
+    // a random shift of 5 to 20 bits, selected by the top 4 bits of the state
+    let shift = (state >> 59) + 5;
+    let word = ((state >> shift) ^ state).wrapping_mul(RXS_M_XS_MULTIPLIER);
+    (word >> 43) ^ word
+}
+
+/// Recovers the state that `rxs_m_xs` permuted to produce `output`
+///
+/// # Examples
+///
+/// ```
+/// use pcg::output::{inverse_rxs_m_xs, rxs_m_xs};
+///
+/// let state = 0x853c_49e6_748f_ea9b;
+/// assert_eq!(inverse_rxs_m_xs(rxs_m_xs(state)), state);
+/// ```
+pub fn inverse_rxs_m_xs(output: u64) -> u64 {
+    let word = unxorshift(output, 43);
+    let xor_shifted = word.wrapping_mul(RXS_M_XS_INVERSE);
+
+    // The random shift is at least 5 bits, so the top bits that selected it pass through the
+    // xorshift unchanged
+    let shift = (xor_shifted >> 59) + 5;
+    unxorshift(xor_shifted, shift as u32)
+}
+
+/// Inverts `x ^ (x >> shift)`
+///
+/// Every iteration recovers another `shift` bits of `x`, starting from the most significant bits.
+fn unxorshift(value: u64, shift: u32) -> u64 {
+    let mut result = value;
+    for _ in 0..(64 / shift) {
+        result = value ^ (result >> shift);
+    }
+    result
+}
+
+/// An output permutation that can be plugged into `Pcg`
+///
+/// Implementations permute the 64-bit LCG state that precedes each step into an output value of
+/// `BITS` bits, stored in the low bits of the returned `u64`.
+pub trait OutputFn: Copy + Clone + fmt::Debug + Default {
+    /// The number of bits of output each step produces, which is either 32 or 64
+    const BITS: u32;
+
+    /// Permutes a state into an output value
+    fn permute(state: u64) -> u64;
+}
+
+/// The XSH RR permutation, which is used by the reference `pcg32` and is the default for `Pcg`
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct XshRr;
+
+impl OutputFn for XshRr {
+    const BITS: u32 = 32;
+
+    fn permute(state: u64) -> u64 {
+        u64::from(xsh_rr(state))
+    }
+}
+
+/// The XSH RS permutation, which trades a little quality for speed compared to XSH RR
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct XshRs;
+
+impl OutputFn for XshRs {
+    const BITS: u32 = 32;
+
+    fn permute(state: u64) -> u64 {
+        u64::from(xsh_rs(state))
+    }
+}
+
+/// The invertible RXS M XS permutation, which produces 64 bits of output per step
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct RxsMXs;
+
+impl RxsMXs {
+    /// Recovers the state that produced an output value
+    pub fn inverse(output: u64) -> u64 {
+        inverse_rxs_m_xs(output)
+    }
+}
+
+impl OutputFn for RxsMXs {
+    const BITS: u32 = 64;
+
+    fn permute(state: u64) -> u64 {
+        rxs_m_xs(state)
+    }
+}
+
+/// The output permutations that can be selected at runtime with `PcgBuilder::output`
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub enum OutputKind {
+    /// The XSH RR permutation, which is the default for `Pcg`
+    #[default]
+    XshRr,
+    /// The XSH RS permutation
+    XshRs,
+    /// The 64-bit RXS M XS permutation
+    RxsMXs,
+}
+
+/// A `Pcg` whose output permutation is picked at runtime rather than by a type parameter
+///
+/// Each variant wraps the generator for one `OutputKind`, and every method dispatches to it, so
+/// code can switch between permutations without being generic over `OutputFn`.
+///
+/// # Examples
+///
+/// ```
+/// use pcg::output::{DynPcg, OutputKind};
+/// use pcg::PcgBuilder;
+/// use rand_core::RngCore;
+///
+/// let mut rng: DynPcg = PcgBuilder::new().seed(42).output(OutputKind::RxsMXs);
+/// assert_eq!(rng.kind(), OutputKind::RxsMXs);
+/// let value = rng.next_u64();
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum DynPcg {
+    /// A generator that uses the XSH RR permutation
+    XshRr(Pcg<XshRr>),
+    /// A generator that uses the XSH RS permutation
+    XshRs(Pcg<XshRs>),
+    /// A generator that uses the RXS M XS permutation
+    RxsMXs(Pcg<RxsMXs>),
+}
+
+impl DynPcg {
+    /// Wraps `pcg` with the output permutation selected by `kind`
+    pub fn new(pcg: Pcg, kind: OutputKind) -> DynPcg {
+        match kind {
+            OutputKind::XshRr => DynPcg::XshRr(pcg),
+            OutputKind::XshRs => DynPcg::XshRs(pcg.with_output()),
+            OutputKind::RxsMXs => DynPcg::RxsMXs(pcg.with_output()),
+        }
+    }
+
+    /// Returns the output permutation the generator uses
+    pub fn kind(&self) -> OutputKind {
+        match self {
+            DynPcg::XshRr(_) => OutputKind::XshRr,
+            DynPcg::XshRs(_) => OutputKind::XshRs,
+            DynPcg::RxsMXs(_) => OutputKind::RxsMXs,
+        }
+    }
+}
+
+impl RngCore for DynPcg {
+    fn next_u32(&mut self) -> u32 {
+        match self {
+            DynPcg::XshRr(pcg) => pcg.next_u32(),
+            DynPcg::XshRs(pcg) => pcg.next_u32(),
+            DynPcg::RxsMXs(pcg) => pcg.next_u32(),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self {
+            DynPcg::XshRr(pcg) => pcg.next_u64(),
+            DynPcg::XshRs(pcg) => pcg.next_u64(),
+            DynPcg::RxsMXs(pcg) => pcg.next_u64(),
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        match self {
+            DynPcg::XshRr(pcg) => pcg.fill_bytes(dest),
+            DynPcg::XshRs(pcg) => pcg.fill_bytes(dest),
+            DynPcg::RxsMXs(pcg) => pcg.fill_bytes(dest),
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// A PCG generator that uses the invertible RXS M XS output permutation
+///
+/// This natively produces 64-bit values, matching the reference `pcg64_once_insecure`
+/// (`pcg_setseq_64_rxs_m_xs_64`). Since the output permutation is a bijection, each output can be
+/// mapped back to the state that produced it with `inverse_output`, which is useful for
+/// reversible simulations and testing.
+///
+/// # Examples
+///
+/// ```
+/// use pcg::{Pcg, PcgRxsMXs};
+/// use rand_core::RngCore;
+///
+/// let mut rng: PcgRxsMXs = Pcg::new(42, 54).with_output();
+/// let state = rng.state();
+/// assert_eq!(PcgRxsMXs::inverse_output(rng.next_u64()), state);
+/// ```
+pub type PcgRxsMXs = Pcg<RxsMXs>;
+
+impl Pcg<RxsMXs> {
+    /// Recovers the state that produced an output value
+    pub fn inverse_output(output: u64) -> u64 {
+        inverse_rxs_m_xs(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inverse_rxs_m_xs() {
+        let mut x: u64 = 0;
+        for _ in 0..100_000 {
+            assert_eq!(inverse_rxs_m_xs(rxs_m_xs(x)), x);
+            x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        }
+        for &x in [0, 1, u64::MAX, 1 << 63, 0x5555_5555_5555_5555].iter() {
+            assert_eq!(inverse_rxs_m_xs(rxs_m_xs(x)), x);
+        }
+    }
+
+    #[test]
+    fn test_inverse_output_recovers_state() {
+        let mut rng: PcgRxsMXs = Pcg::new(42, 54).with_output();
+        for _ in 0..100 {
+            let state = rng.state();
+            assert_eq!(PcgRxsMXs::inverse_output(rng.next_u64()), state);
+            assert_eq!(RxsMXs::inverse(RxsMXs::permute(state)), state);
+        }
+    }
+
+    /// Checks that every bit of the generated values is set roughly half of the time
+    fn assert_balanced<O: OutputFn>(mut rng: Pcg<O>) {
+        let samples = 10_000;
+        let mut counts = [0usize; 64];
+
+        for _ in 0..samples {
+            let value = rng.next_u64();
+            for (bit, count) in counts.iter_mut().enumerate() {
+                *count += ((value >> bit) & 1) as usize;
+            }
+        }
+
+        for (bit, &count) in counts.iter().enumerate() {
+            let ratio = count as f64 / samples as f64;
+            assert!(
+                (0.45..0.55).contains(&ratio),
+                "bit {} of {:?} was set in {} of samples",
+                bit,
+                O::default(),
+                ratio
+            );
+        }
+    }
+
+    #[test]
+    fn test_permutations_are_balanced() {
+        assert_balanced(Pcg::new(42, 54).with_output::<XshRr>());
+        assert_balanced(Pcg::new(42, 54).with_output::<XshRs>());
+        assert_balanced(Pcg::new(42, 54).with_output::<RxsMXs>());
+    }
+
+    #[test]
+    fn test_permutations_are_distinct() {
+        let rng = Pcg::new(42, 54);
+        let mut xsh_rr = rng.clone().with_output::<XshRr>();
+        let mut xsh_rs = rng.clone().with_output::<XshRs>();
+        let mut rxs_m_xs = rng.with_output::<RxsMXs>();
+
+        let a: Vec<u64> = (0..8).map(|_| xsh_rr.next_u64()).collect();
+        let b: Vec<u64> = (0..8).map(|_| xsh_rs.next_u64()).collect();
+        let c: Vec<u64> = (0..8).map(|_| rxs_m_xs.next_u64()).collect();
+        assert_ne!(a, b);
+        assert_ne!(b, c);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_dyn_pcg_matches_static() {
+        let mut dynamic = DynPcg::new(Pcg::new(42, 54), OutputKind::XshRs);
+        let mut fixed = Pcg::new(42, 54).with_output::<XshRs>();
+        assert_eq!(dynamic.kind(), OutputKind::XshRs);
+        for _ in 0..10 {
+            assert_eq!(dynamic.next_u64(), fixed.next_u64());
+        }
+        assert_eq!(dynamic, DynPcg::XshRs(fixed));
+    }
+
+    #[test]
+    fn test_rxs_m_xs_is_native_64_bit() {
+        let mut rng: PcgRxsMXs = Pcg::new(42, 54).with_output();
+        let state = rng.state();
+        assert_eq!(rng.next_u64(), rxs_m_xs(state));
+        assert_eq!(
+            rng.distance(&Pcg::new(42, 54).with_output()),
+            Some(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn test_xsh_rr_matches_pcg() {
+        let mut rng = Pcg::new(42, 54);
+        let state = rng.state();
+        assert_eq!(rng.next_u32(), xsh_rr(state));
+    }
+}
@@ -0,0 +1,87 @@
+//! Parallel bulk generation with `rayon`, enabled with the `rayon` feature.
+//!
+//! The slice is split into fixed-size chunks, and each chunk is filled on its own thread by a copy
+//! of the generator that has been advanced to where that chunk starts. Since `advance` jumps in
+//! `O(log n)` time, every chunk starts independently, and the output is the same as filling the
+//! whole slice on one thread.
+
+use crate::{OutputFn, Pcg};
+use rayon::prelude::*;
+
+/// The number of values in each chunk that `par_fill_u64` hands to a thread
+const CHUNK_LEN: usize = 1 << 14;
+
+impl<O: OutputFn> Pcg<O> {
+    /// Fills a slice with values from `next_u64`, splitting the work across rayon's thread pool.
+    ///
+    /// `dest` is split into chunks of 16384 values. The chunk at index `i` is filled by a copy of
+    /// the generator advanced past the `16384 * i` values before it, so the result is
+    /// bit-identical to `fill_u64` for any number of threads, and the generator is left in the
+    /// same state afterwards. Slices of up to one chunk are filled on the calling thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut a = Pcg::new(42, 54);
+    /// let mut b = a.clone();
+    /// let mut parallel = vec![0u64; 100_000];
+    /// let mut sequential = vec![0u64; 100_000];
+    /// a.par_fill_u64(&mut parallel);
+    /// b.fill_u64(&mut sequential);
+    /// assert_eq!(parallel, sequential);
+    /// assert_eq!(a, b);
+    /// ```
+    pub fn par_fill_u64(&mut self, dest: &mut [u64]) {
+        if dest.len() <= CHUNK_LEN {
+            self.fill_u64(dest);
+            return;
+        }
+
+        // Permutations that produce 32 bits take two steps for every 64-bit value
+        let steps_per_value: u64 = if O::BITS == 64 { 1 } else { 2 };
+        let (state, inc) = (self.state, self.inc);
+        dest.par_chunks_mut(CHUNK_LEN)
+            .enumerate()
+            .for_each(|(i, chunk)| {
+                let mut rng: Pcg<O> = Pcg::from_parts(state, inc);
+                rng.advance((i * CHUNK_LEN) as u64 * steps_per_value);
+                rng.fill_u64(chunk);
+            });
+
+        // Jump to the end directly, rather than with `advance`, so that the deviate cached by
+        // `next_normal` is kept just like it is by `fill_u64`
+        let (mult, plus) = self.jump(dest.len() as u64 * steps_per_value);
+        self.state = mult.wrapping_mul(self.state).wrapping_add(plus);
+        self.record_generated(dest.len() as u64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::{RxsMXs, XshRs};
+
+    fn check_against_sequential<O: OutputFn>(rng: Pcg<O>) {
+        for &len in [0, 1, CHUNK_LEN, CHUNK_LEN + 1, 3 * CHUNK_LEN + 17].iter() {
+            let mut a = rng.clone();
+            let mut b = rng.clone();
+            let mut parallel = vec![0u64; len];
+            let mut sequential = vec![0u64; len];
+            a.par_fill_u64(&mut parallel);
+            b.fill_u64(&mut sequential);
+            assert_eq!(parallel, sequential);
+            assert_eq!(a, b);
+            #[cfg(feature = "trace")]
+            assert_eq!(a.generated_count(), b.generated_count());
+        }
+    }
+
+    #[test]
+    fn test_par_fill_u64_matches_fill_u64() {
+        check_against_sequential(Pcg::new(42, 54));
+        check_against_sequential(Pcg::new(42, 54).with_output::<XshRs>());
+        check_against_sequential(Pcg::new(42, 54).with_output::<RxsMXs>());
+    }
+}
@@ -0,0 +1,182 @@
+//! A PCG generator with 128 bits of state and 64 bits of output.
+
+use crate::consts::{INCREMENTOR_128, INIT_INC_128, INIT_STATE_128};
+
+#[cfg(feature = "std")]
+use std::{fmt, num::Wrapping};
+
+#[cfg(not(feature = "std"))]
+use core::{fmt, num::Wrapping};
+
+use rand_core::{impls, Error, RngCore, SeedableRng};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// The `Pcg64` state struct contains state information for the 128-bit PCG generator.
+///
+/// Unlike `Pcg`, which has 64 bits of state and produces 32-bit outputs, this generator keeps 128
+/// bits of state and applies the XSL RR permutation to produce full-entropy 64-bit outputs. It
+/// matches the reference `pcg64` (`pcg_setseq_128_xsl_rr_64`) generator.
+#[derive(Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Pcg64 {
+    state: u128,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize_increment"))]
+    inc: u128,
+}
+
+impl Pcg64 {
+    /// The base-2 logarithm of the generator's period.
+    ///
+    /// The underlying LCG is full-period for any odd increment, which every constructor
+    /// guarantees, so every stream visits all `2^128` states before repeating.
+    pub const PERIOD_LOG2: u32 = 128;
+
+    /// Returns the base-2 logarithm of the generator's period, `PERIOD_LOG2`.
+    pub fn period_log2(&self) -> u32 {
+        Self::PERIOD_LOG2
+    }
+
+    /// Constructs a new `Pcg64` state struct with a particular seed and sequence.
+    ///
+    /// The `seed` param supplies an initial state for the RNG, and the `seq` param functionally
+    /// acts as a stream ID. This follows the seeding procedure of the reference
+    /// `pcg64_srandom_r`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg64;
+    ///
+    /// let mut rng = Pcg64::new(0, 0);
+    /// ```
+    pub fn new(seed: u128, seq: u128) -> Pcg64 {
+        let mut pcg = Pcg64 {
+            state: 0,
+            inc: (seq << 1) | 1,
+        };
+        pcg.step();
+        pcg.state = pcg.state.wrapping_add(seed);
+        pcg.step();
+        pcg
+    }
+
+    /// Advances the internal state of the LCG by a single step
+    fn step(&mut self) {
+        self.state = (Wrapping(self.state) * Wrapping(INCREMENTOR_128) + Wrapping(self.inc)).0;
+    }
+}
+
+/// Deserializes an increment, forcing it to be odd so that a deserialized generator always has a
+/// full period
+#[cfg(feature = "serde")]
+fn deserialize_increment<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u128, D::Error> {
+    u128::deserialize(deserializer).map(|inc| inc | 1)
+}
+
+impl Default for Pcg64 {
+    fn default() -> Self {
+        Pcg64 {
+            state: INIT_STATE_128,
+            inc: INIT_INC_128,
+        }
+    }
+}
+
+#[cfg(not(feature = "secure-debug"))]
+impl fmt::Debug for Pcg64 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Pcg64")
+            .field("state", &self.state)
+            .field("inc", &self.inc)
+            .finish()
+    }
+}
+
+/// Prints `Pcg64 { .. }` without any field values, so that the state can't leak into logs
+#[cfg(feature = "secure-debug")]
+impl fmt::Debug for Pcg64 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Pcg64").finish_non_exhaustive()
+    }
+}
+
+impl RngCore for Pcg64 {
+    /// Generates a 32-bit value from the high 32 bits of `next_u64`
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// Generates a 64-bit value using the XSL RR permutation
+    ///
+    /// As in the reference implementation, the state is advanced first and the output is computed
+    /// from the new state.
+    fn next_u64(&mut self) -> u64 {
+        self.step();
+        let rot = (self.state >> 122) as u32;
+        let xor_folded = ((self.state >> 64) as u64) ^ (self.state as u64);
+        xor_folded.rotate_right(rot)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl SeedableRng for Pcg64 {
+    /// The seed is interpreted as a big-endian `u128`
+    type Seed = [u8; 16];
+
+    fn from_seed(seed: Self::Seed) -> Pcg64 {
+        Pcg64::new(u128::from_be_bytes(seed), INIT_INC_128)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_answer() {
+        let mut rng = Pcg64::new(42, 54);
+
+        // Output of the reference `pcg64_srandom_r(&rng, 42, 54)` followed by `pcg64_random_r`
+        let expected: [u64; 6] = [
+            0x86b1_da1d_7206_2b68,
+            0x1304_aa46_c985_3d39,
+            0xa367_0e9e_0dd5_0358,
+            0xf909_0e52_9a7d_ae00,
+            0xc85b_9fd8_3799_6f2c,
+            0x6061_21f8_e391_9196,
+        ];
+        for &value in expected.iter() {
+            assert_eq!(rng.next_u64(), value);
+        }
+    }
+
+    #[test]
+    fn test_next_u32_is_high_bits() {
+        let mut a = Pcg64::new(42, 54);
+        let mut b = a.clone();
+        assert_eq!(u64::from(a.next_u32()), b.next_u64() >> 32);
+    }
+
+    #[test]
+    fn test_period() {
+        const _: () = assert!(Pcg64::PERIOD_LOG2 == 128);
+        assert_eq!(Pcg64::default().period_log2(), 128);
+    }
+
+    #[test]
+    fn test_from_seed() {
+        let mut seed = [0u8; 16];
+        seed[15] = 42;
+        assert_eq!(Pcg64::from_seed(seed), Pcg64::new(42, INIT_INC_128));
+    }
+}
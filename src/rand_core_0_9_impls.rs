@@ -0,0 +1,111 @@
+//! Support for the `rand_core` 0.9 traits, enabled with the `rand_core_0_9` feature.
+//!
+//! The crate is built around `rand_core` 0.5, but `rand` 0.9 and the crates built on it expect
+//! the 0.9 traits. With this feature, `Pcg` implements both versions, and the 0.9 impls produce
+//! exactly the same values as the 0.5 ones. `rand_core` 0.9 provides `TryRngCore` for every
+//! `RngCore`, so generators can also be used wherever a fallible generator is expected.
+
+use crate::{OutputFn, Pcg};
+use rand_core_0_9::{RngCore, SeedableRng};
+
+impl<O: OutputFn> RngCore for Pcg<O> {
+    fn next_u32(&mut self) -> u32 {
+        rand_core::RngCore::next_u32(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        rand_core::RngCore::next_u64(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        rand_core::RngCore::fill_bytes(self, dest)
+    }
+}
+
+/// Seeds a generator the same way as the `rand_core` 0.5 `SeedableRng` impl
+impl SeedableRng for Pcg {
+    type Seed = <Pcg as rand_core::SeedableRng>::Seed;
+
+    fn from_seed(seed: Self::Seed) -> Pcg {
+        rand_core::SeedableRng::from_seed(seed)
+    }
+
+    fn seed_from_u64(state: u64) -> Pcg {
+        rand_core::SeedableRng::seed_from_u64(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::RxsMXs;
+    use rand_0_9::Rng;
+
+    #[test]
+    fn test_random_matches_rand_core_0_5() {
+        let mut rng = Pcg::new(42, 54);
+        let mut expected = rng.clone();
+        for _ in 0..100 {
+            assert_eq!(
+                rng.random::<u32>(),
+                rand_core::RngCore::next_u32(&mut expected)
+            );
+        }
+        for _ in 0..100 {
+            assert_eq!(
+                rng.random::<u64>(),
+                rand_core::RngCore::next_u64(&mut expected)
+            );
+        }
+        assert_eq!(rng, expected);
+
+        let mut rng = Pcg::new(42, 54).with_output::<RxsMXs>();
+        let mut expected = rng.clone();
+        assert_eq!(
+            rng.random::<u32>(),
+            rand_core::RngCore::next_u32(&mut expected)
+        );
+    }
+
+    #[test]
+    fn test_fill_bytes_matches_rand_core_0_5() {
+        let mut rng = Pcg::new(42, 54);
+        let mut expected = rng.clone();
+        let mut bytes = [0u8; 21];
+        let mut expected_bytes = [0u8; 21];
+        RngCore::fill_bytes(&mut rng, &mut bytes);
+        rand_core::RngCore::fill_bytes(&mut expected, &mut expected_bytes);
+        assert_eq!(bytes, expected_bytes);
+        assert_eq!(rng, expected);
+    }
+
+    #[test]
+    fn test_rng_methods() {
+        let mut rng = Pcg::new(42, 54);
+        for _ in 0..1000 {
+            assert!((10..20).contains(&rng.random_range(10..20)));
+        }
+        assert!(!rng.random_bool(0.0));
+        assert!(rng.random_bool(1.0));
+    }
+
+    #[test]
+    fn test_seedable_rng_matches_rand_core_0_5() {
+        let seed = [0, 1, 2, 3, 4, 5, 6, 7];
+        assert_eq!(
+            <Pcg as SeedableRng>::from_seed(seed),
+            <Pcg as rand_core::SeedableRng>::from_seed(seed)
+        );
+        assert_eq!(
+            <Pcg as SeedableRng>::seed_from_u64(42),
+            <Pcg as rand_core::SeedableRng>::seed_from_u64(42)
+        );
+
+        let mut source = Pcg::new(1, 2);
+        let mut expected_source = source.clone();
+        assert_eq!(
+            <Pcg as SeedableRng>::from_rng(&mut source),
+            <Pcg as rand_core::SeedableRng>::from_rng(&mut expected_source).unwrap()
+        );
+    }
+}
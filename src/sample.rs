@@ -0,0 +1,750 @@
+//! Sampling methods for common value types and distributions that don't depend on `rand`.
+
+use crate::{OutputFn, Pcg};
+use rand_core::RngCore;
+
+#[cfg(feature = "std")]
+use std::{
+    f64::consts::PI,
+    ops::{Range, RangeInclusive},
+};
+
+#[cfg(not(feature = "std"))]
+use core::ops::{Range, RangeInclusive};
+
+/// `2^-53`, the gap between consecutive `f64` values in `[0.5, 1)`
+const F64_SCALE: f64 = 1.0 / (1u64 << 53) as f64;
+
+/// `2^-24`, the gap between consecutive `f32` values in `[0.5, 1)`
+const F32_SCALE: f32 = 1.0 / (1u32 << 24) as f32;
+
+/// The characters `alphanumeric` draws from
+const ALPHANUMERIC: &[u8; 62] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+impl<O: OutputFn> Pcg<O> {
+    /// Generates a uniformly distributed `i32` by reinterpreting the bits of `next_u32`.
+    pub fn next_i32(&mut self) -> i32 {
+        self.next_u32() as i32
+    }
+
+    /// Generates a uniformly distributed `i64` by reinterpreting the bits of `next_u64`.
+    pub fn next_i64(&mut self) -> i64 {
+        self.next_u64() as i64
+    }
+
+    /// Generates a uniformly distributed `u128` from two calls to `next_u64`.
+    ///
+    /// The first value makes up the high 64 bits and the second makes up the low 64 bits, the
+    /// same layout `next_u64` uses for 32-bit halves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    /// use rand_core::RngCore;
+    ///
+    /// let mut a = Pcg::new(42, 54);
+    /// let mut b = a.clone();
+    /// let high = u128::from(b.next_u64());
+    /// let low = u128::from(b.next_u64());
+    /// assert_eq!(a.next_u128(), (high << 64) | low);
+    /// ```
+    pub fn next_u128(&mut self) -> u128 {
+        let high = u128::from(self.next_u64());
+        let low = u128::from(self.next_u64());
+        (high << 64) | low
+    }
+
+    /// Generates two values with `next_u64`, for algorithms that need a pair of draws at once.
+    ///
+    /// The pair is identical to two sequential calls to `next_u64`, in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    /// use rand_core::RngCore;
+    ///
+    /// let mut a = Pcg::new(42, 54);
+    /// let mut b = a.clone();
+    /// assert_eq!(a.next_u64_pair(), (b.next_u64(), b.next_u64()));
+    /// ```
+    pub fn next_u64_pair(&mut self) -> (u64, u64) {
+        let first = self.next_u64();
+        let second = self.next_u64();
+        (first, second)
+    }
+
+    /// Generates a value with `next_u64` and returns its bytes in little-endian order.
+    ///
+    /// This is the byte order `fill_bytes` uses.
+    pub fn next_u64_le(&mut self) -> [u8; 8] {
+        self.next_u64().to_le_bytes()
+    }
+
+    /// Generates a value with `next_u64` and returns its bytes in big-endian order.
+    pub fn next_u64_be(&mut self) -> [u8; 8] {
+        self.next_u64().to_be_bytes()
+    }
+
+    /// Generates a uniformly distributed value in `range`, which excludes its end.
+    ///
+    /// This uses the same unbiased rejection sampling as `next_bounded_u64`.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `range` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut rng = Pcg::default();
+    /// let roll = rng.range_u64(1..7);
+    /// assert!(roll >= 1 && roll < 7);
+    /// ```
+    pub fn range_u64(&mut self, range: Range<u64>) -> u64 {
+        assert!(range.start < range.end, "cannot sample from an empty range");
+        range.start + self.next_bounded_u64(range.end - range.start)
+    }
+
+    /// Generates a uniformly distributed value in `range`, which includes its end.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `range` is empty.
+    pub fn range_inclusive(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "cannot sample from an empty range");
+        match (end - start).checked_add(1) {
+            Some(span) => start + self.next_bounded_u64(span),
+            // The range covers every `u64`
+            None => self.next_u64(),
+        }
+    }
+
+    /// Generates a uniformly distributed value in `range`, which excludes its end.
+    ///
+    /// The range is offset into the unsigned domain, so negative bounds are handled without
+    /// overflow.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `range` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut rng = Pcg::default();
+    /// let x = rng.range_i64(-10..10);
+    /// assert!(x >= -10 && x < 10);
+    /// ```
+    pub fn range_i64(&mut self, range: Range<i64>) -> i64 {
+        assert!(range.start < range.end, "cannot sample from an empty range");
+        let span = range.end.wrapping_sub(range.start) as u64;
+        range.start.wrapping_add(self.next_bounded_u64(span) as i64)
+    }
+
+    /// Fills `buf` with ASCII characters drawn uniformly from `[A-Za-z0-9]`.
+    ///
+    /// Each character is chosen with `next_bounded_u32`, so none of them is more likely than the
+    /// others. This is the allocation-free version of `alphanumeric`.
+    pub fn alphanumeric_into(&mut self, buf: &mut [u8]) {
+        for byte in buf.iter_mut() {
+            *byte = ALPHANUMERIC[self.next_bounded_u32(ALPHANUMERIC.len() as u32) as usize];
+        }
+    }
+
+    /// Generates a string of `len` characters drawn uniformly from `[A-Za-z0-9]`.
+    ///
+    /// This requires the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut rng = Pcg::default();
+    /// let name = rng.alphanumeric(12);
+    /// assert_eq!(name.len(), 12);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn alphanumeric(&mut self, len: usize) -> String {
+        let mut buf = vec![0u8; len];
+        self.alphanumeric_into(&mut buf);
+        buf.into_iter().map(char::from).collect()
+    }
+
+    /// Generates a uniformly distributed `f64` in the range `[0, 1)`.
+    ///
+    /// This uses the top 53 bits of `next_u64` as the mantissa, so every output is a multiple of
+    /// `2^-53`. This is the same construction as `rand`'s `Standard` distribution, so
+    /// `rng.next_f64()` and `rng.gen::<f64>()` return the same value for the same state.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut rng = Pcg::default();
+    /// let x = rng.next_f64();
+    /// assert!(x >= 0.0 && x < 1.0);
+    /// ```
+    pub fn next_f64(&mut self) -> f64 {
+        unit_f64(self.next_u64())
+    }
+
+    /// Generates a uniformly distributed `f32` in the range `[0, 1)`.
+    ///
+    /// This uses the top 24 bits of `next_u32` as the mantissa, so every output is a multiple of
+    /// `2^-24`. As with `next_f64`, this matches `rand`'s `Standard` distribution.
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 * F32_SCALE
+    }
+
+    /// Generates a uniformly distributed `f64` in the open range `(0, 1)`.
+    ///
+    /// This uses the top 52 bits of `next_u64` and offsets them by half a step, so the output is
+    /// never exactly 0 or 1. It is handy when the value is passed to functions like `ln`.
+    pub fn next_f64_open(&mut self) -> f64 {
+        open_unit_f64(self.next_u64())
+    }
+
+    /// Returns `true` with probability `p`.
+    ///
+    /// This compares `next_f64` against `p`, so `p <= 0.0` always returns `false` and `p >= 1.0`
+    /// always returns `true`. One value is consumed either way, so the generator advances the
+    /// same amount regardless of `p`.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `p` is NaN.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut rng = Pcg::default();
+    /// let heads = rng.gen_bool(0.5);
+    /// ```
+    pub fn gen_bool(&mut self, p: f64) -> bool {
+        assert!(!p.is_nan(), "the probability must not be NaN");
+        self.next_f64() < p
+    }
+
+    /// Generates a normally distributed `f64` with the given mean and standard deviation.
+    ///
+    /// This uses the Box-Muller transform on top of `next_f64`. Each transform produces two
+    /// independent deviates, so the second one is cached and returned by the next call, making
+    /// every other call cheap. The cached deviate is discarded whenever the generator is
+    /// repositioned with `advance`, `set_state`, `set_stream`, or `reset`.
+    ///
+    /// The cached deviate isn't part of the state compared by `PartialEq` and `Hash`, so two
+    /// generators that compare equal can still return different values here if only one of them
+    /// holds a cached deviate. Checkpoints taken with `state` and `from_state`, or through
+    /// serialization, don't keep it either.
+    ///
+    /// If `std_dev` is 0, this returns exactly `mean` without consuming any randomness. This
+    /// requires the `std` feature.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `std_dev` is negative or NaN.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut rng = Pcg::default();
+    /// let height = rng.next_normal(170.0, 10.0);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn next_normal(&mut self, mean: f64, std_dev: f64) -> f64 {
+        assert!(
+            std_dev >= 0.0,
+            "the standard deviation must be non-negative, got {}",
+            std_dev
+        );
+
+        if std_dev == 0.0 {
+            return mean;
+        }
+        mean + std_dev * self.next_standard_normal()
+    }
+
+    /// Generates an exponentially distributed `f64` with the given rate `lambda`.
+    ///
+    /// This uses the inverse CDF, `-ln(1 - u) / lambda`, where `u` comes from `next_f64`. Since
+    /// `u` is in `[0, 1)`, `1 - u` is never 0 and the result is always finite. This requires the
+    /// `std` feature.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `lambda` is not positive (including NaN).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut rng = Pcg::default();
+    /// let wait = rng.next_exponential(0.5);
+    /// assert!(wait >= 0.0);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn next_exponential(&mut self, lambda: f64) -> f64 {
+        assert!(lambda > 0.0, "lambda must be positive, got {}", lambda);
+        -(1.0 - self.next_f64()).ln() / lambda
+    }
+
+    /// Generates the number of failed Bernoulli trials with success probability `p` before the
+    /// first success.
+    ///
+    /// This uses the inverse CDF, `floor(ln(1 - u) / ln(1 - p))`, where `u` comes from
+    /// `next_f64`. If `p` is at least 1, the first trial always succeeds and this returns 0
+    /// without consuming any output. Results too large for a `u64` saturate at `u64::MAX`. This
+    /// requires the `std` feature.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `p` is not positive (including NaN), since no trial would ever succeed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut rng = Pcg::default();
+    /// let failures = rng.next_geometric(0.25);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn next_geometric(&mut self, p: f64) -> u64 {
+        assert!(p > 0.0, "p must be positive, got {}", p);
+        if p >= 1.0 {
+            return 0;
+        }
+        // `ln_1p` keeps `ln(1 - p)` accurate when `p` is tiny
+        ((1.0 - self.next_f64()).ln() / (-p).ln_1p()).floor() as u64
+    }
+
+    /// Generates a point uniformly distributed on the unit circle, as `(x, y)`.
+    ///
+    /// The point lies on the circle itself rather than anywhere in the disk it encloses. Its angle
+    /// is `2 * pi * u`, where `u` comes from `next_f64`. This requires the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut rng = Pcg::default();
+    /// let (x, y) = rng.unit_circle();
+    /// assert!((x * x + y * y - 1.0).abs() < 1e-12);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn unit_circle(&mut self) -> (f64, f64) {
+        let angle = 2.0 * PI * self.next_f64();
+        let (y, x) = angle.sin_cos();
+        (x, y)
+    }
+
+    /// Generates a point uniformly distributed on the surface of the unit sphere, as `(x, y, z)`.
+    ///
+    /// The point lies on the sphere itself rather than anywhere in the ball it encloses. By
+    /// Archimedes' hat-box theorem, `z` is uniform in `[-1, 1)`, and the point's angle around the
+    /// z axis is uniform as well. This requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn unit_sphere(&mut self) -> (f64, f64, f64) {
+        let z = 2.0 * self.next_f64() - 1.0;
+        let (x, y) = self.unit_circle();
+        let radius = (1.0 - z * z).sqrt();
+        (radius * x, radius * y, z)
+    }
+
+    /// Generates a normally distributed `f64` with a mean of 0 and a standard deviation of 1
+    #[cfg(feature = "std")]
+    fn next_standard_normal(&mut self) -> f64 {
+        if let Some(z) = self.cached_normal.take() {
+            return z;
+        }
+
+        // `u1` must be non-zero since it is passed to `ln`
+        let (first, second) = self.next_u64_pair();
+        let u1 = open_unit_f64(first);
+        let u2 = unit_f64(second);
+        let radius = (-2.0 * u1.ln()).sqrt();
+        let theta = 2.0 * PI * u2;
+        self.cached_normal = Some(radius * theta.sin());
+        radius * theta.cos()
+    }
+}
+
+/// Maps the top 53 bits of `bits` to an `f64` in `[0, 1)`, as `next_f64` does
+fn unit_f64(bits: u64) -> f64 {
+    (bits >> 11) as f64 * F64_SCALE
+}
+
+/// Maps the top 52 bits of `bits` to an `f64` in `(0, 1)`, as `next_f64_open` does
+fn open_unit_f64(bits: u64) -> f64 {
+    ((bits >> 12) as f64 + 0.5) * (2.0 * F64_SCALE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::RxsMXs;
+    use rand::Rng;
+
+    const SAMPLES: usize = 100_000;
+
+    #[test]
+    fn test_next_f64_bounds_and_mean() {
+        let mut rng = Pcg::new(42, 54);
+        let mut sum = 0.0;
+        for _ in 0..SAMPLES {
+            let x = rng.next_f64();
+            assert!((0.0..1.0).contains(&x));
+            sum += x;
+        }
+        assert!((sum / SAMPLES as f64 - 0.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_next_f32_bounds_and_mean() {
+        let mut rng = Pcg::new(42, 54);
+        let mut sum = 0.0;
+        for _ in 0..SAMPLES {
+            let x = rng.next_f32();
+            assert!((0.0..1.0).contains(&x));
+            sum += f64::from(x);
+        }
+        assert!((sum / SAMPLES as f64 - 0.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_next_f64_open_bounds_and_mean() {
+        let mut rng = Pcg::new(42, 54);
+        let mut sum = 0.0;
+        for _ in 0..SAMPLES {
+            let x = rng.next_f64_open();
+            assert!(x > 0.0 && x < 1.0);
+            sum += x;
+        }
+        assert!((sum / SAMPLES as f64 - 0.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_float_extremes() {
+        // The RXS M XS permutation is invertible, so states that produce the smallest and largest
+        // outputs can be constructed directly
+        let mut rng = Pcg::default().with_output::<RxsMXs>();
+
+        rng.set_state(RxsMXs::inverse(0));
+        assert_eq!(rng.clone().next_f64(), 0.0);
+        assert!(rng.next_f64_open() > 0.0);
+
+        rng.set_state(RxsMXs::inverse(u64::MAX));
+        assert!(rng.clone().next_f64() < 1.0);
+        assert!(rng.next_f64_open() < 1.0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_next_normal_moments() {
+        let mut rng = Pcg::new(42, 54);
+        let (mean, std_dev) = (3.0, 2.0);
+        let values: Vec<f64> = (0..SAMPLES)
+            .map(|_| rng.next_normal(mean, std_dev))
+            .collect();
+
+        let sample_mean = values.iter().sum::<f64>() / SAMPLES as f64;
+        let sample_variance = values
+            .iter()
+            .map(|x| (x - sample_mean).powi(2))
+            .sum::<f64>()
+            / SAMPLES as f64;
+        assert!(
+            (sample_mean - mean).abs() < 0.05,
+            "mean was {}",
+            sample_mean
+        );
+        assert!(
+            (sample_variance - std_dev * std_dev).abs() < 0.1,
+            "variance was {}",
+            sample_variance
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_next_normal_zero_std_dev() {
+        let mut rng = Pcg::new(42, 54);
+        assert_eq!(rng.next_normal(1.5, 0.0), 1.5);
+        assert_eq!(rng, Pcg::new(42, 54));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_next_normal_caches_second_deviate() {
+        let mut rng = Pcg::new(42, 54);
+        rng.next_normal(0.0, 1.0);
+        let after_first = rng.clone();
+        rng.next_normal(0.0, 1.0);
+        assert_eq!(rng, after_first);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[should_panic]
+    fn test_next_normal_negative_std_dev() {
+        Pcg::default().next_normal(0.0, -1.0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_next_exponential_mean() {
+        let mut rng = Pcg::new(42, 54);
+        for &lambda in [0.5, 1.0, 4.0].iter() {
+            let mut sum = 0.0;
+            for _ in 0..SAMPLES {
+                let x = rng.next_exponential(lambda);
+                assert!(x.is_finite() && x >= 0.0);
+                sum += x;
+            }
+            let mean = sum / SAMPLES as f64;
+            assert!(
+                (mean - 1.0 / lambda).abs() < 0.02 / lambda,
+                "mean was {} for lambda {}",
+                mean,
+                lambda
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[should_panic]
+    fn test_next_exponential_non_positive_lambda() {
+        Pcg::default().next_exponential(0.0);
+    }
+
+    #[test]
+    fn test_gen_bool_rates() {
+        let mut rng = Pcg::new(42, 54);
+        for &(p, expected) in [(0.0, 0.0), (-1.0, 0.0), (1.0, 1.0), (2.0, 1.0), (0.5, 0.5)].iter() {
+            let hits = (0..SAMPLES).filter(|_| rng.gen_bool(p)).count();
+            let rate = hits as f64 / SAMPLES as f64;
+            assert!(
+                (rate - expected).abs() < 0.01,
+                "rate was {} for p = {}",
+                rate,
+                p
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_bool_nan() {
+        Pcg::default().gen_bool(f64::NAN);
+    }
+
+    #[test]
+    fn test_signed_matches_unsigned() {
+        let mut a = Pcg::new(42, 54);
+        let mut b = a.clone();
+        for _ in 0..100 {
+            assert_eq!(a.next_i32(), i32::from_ne_bytes(b.next_u32().to_ne_bytes()));
+            assert_eq!(a.next_i64(), i64::from_ne_bytes(b.next_u64().to_ne_bytes()));
+        }
+    }
+
+    #[test]
+    fn test_next_u128_layout() {
+        let mut a = Pcg::new(42, 54);
+        let mut b = a.clone();
+        for _ in 0..100 {
+            let value = a.next_u128();
+            assert_eq!((value >> 64) as u64, b.next_u64());
+            assert_eq!(value as u64, b.next_u64());
+        }
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_range_single_element() {
+        let mut rng = Pcg::new(42, 54);
+        for &a in [0, 1, 12345, u64::MAX - 1].iter() {
+            assert_eq!(rng.range_u64(a..a + 1), a);
+            assert_eq!(rng.range_inclusive(a..=a), a);
+        }
+        assert_eq!(rng.range_inclusive(u64::MAX..=u64::MAX), u64::MAX);
+        assert_eq!(rng.range_i64(-5..-4), -5);
+        assert_eq!(rng.range_i64(i64::MIN..i64::MIN + 1), i64::MIN);
+    }
+
+    #[test]
+    fn test_range_full() {
+        let mut rng = Pcg::new(42, 54);
+        for _ in 0..100 {
+            assert!(rng.range_u64(0..u64::MAX) < u64::MAX);
+        }
+
+        // The full inclusive range can't be rejected from, so it's just `next_u64`
+        let mut expected = rng.clone();
+        for _ in 0..100 {
+            assert_eq!(rng.range_inclusive(0..=u64::MAX), expected.next_u64());
+        }
+        rng.range_i64(i64::MIN..i64::MAX);
+    }
+
+    #[test]
+    fn test_range_i64_negative() {
+        let mut rng = Pcg::new(42, 54);
+        let mut seen = [false; 10];
+        for _ in 0..1000 {
+            let x = rng.range_i64(-20..-10);
+            assert!((-20..-10).contains(&x));
+            seen[(x + 20) as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+
+        for _ in 0..1000 {
+            let x = rng.range_i64(-3..3);
+            assert!((-3..3).contains(&x));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "empty range")]
+    fn test_range_empty_panics() {
+        Pcg::default().range_u64(5..5);
+    }
+
+    #[test]
+    fn test_floats_match_rand_standard() {
+        let mut rng = Pcg::new(42, 54);
+        for _ in 0..1000 {
+            assert_eq!(rng.clone().gen::<f64>().to_bits(), rng.next_f64().to_bits());
+            assert_eq!(rng.clone().gen::<f32>().to_bits(), rng.next_f32().to_bits());
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_next_geometric_mean() {
+        let mut rng = Pcg::new(42, 54);
+        for &p in [0.1, 0.5, 0.9].iter() {
+            let sum: u64 = (0..SAMPLES).map(|_| rng.next_geometric(p)).sum();
+            let mean = sum as f64 / SAMPLES as f64;
+            let expected = (1.0 - p) / p;
+            assert!(
+                (mean - expected).abs() < 0.02 * expected.max(1.0),
+                "mean was {} for p = {}",
+                mean,
+                p
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_next_geometric_certain_success() {
+        let mut rng = Pcg::new(42, 54);
+        assert_eq!(rng.next_geometric(1.0), 0);
+        assert_eq!(rng.next_geometric(2.0), 0);
+        assert_eq!(rng, Pcg::new(42, 54));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[should_panic]
+    fn test_next_geometric_non_positive_p() {
+        Pcg::default().next_geometric(0.0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_unit_circle_norm() {
+        let mut rng = Pcg::new(42, 54);
+        let (mut sum_x, mut sum_y) = (0.0, 0.0);
+        for _ in 0..SAMPLES {
+            let (x, y) = rng.unit_circle();
+            assert!((x * x + y * y - 1.0).abs() < 1e-12);
+            sum_x += x;
+            sum_y += y;
+        }
+        // Points are spread evenly around the circle, so they average out to the center
+        assert!((sum_x / SAMPLES as f64).abs() < 0.01);
+        assert!((sum_y / SAMPLES as f64).abs() < 0.01);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_unit_sphere_norm() {
+        let mut rng = Pcg::new(42, 54);
+        let mut sum = [0.0; 3];
+        for _ in 0..SAMPLES {
+            let (x, y, z) = rng.unit_sphere();
+            assert!((x * x + y * y + z * z - 1.0).abs() < 1e-12);
+            sum[0] += x;
+            sum[1] += y;
+            sum[2] += z;
+        }
+        for &s in sum.iter() {
+            assert!((s / SAMPLES as f64).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_alphanumeric_into() {
+        let mut rng = Pcg::new(42, 54);
+        let mut buf = [0u8; 1000];
+        rng.alphanumeric_into(&mut buf);
+        assert!(buf.iter().all(u8::is_ascii_alphanumeric));
+
+        // With this many characters, every one of them should show up
+        let mut seen = [false; 128];
+        for &byte in buf.iter() {
+            seen[byte as usize] = true;
+        }
+        assert!(ALPHANUMERIC.iter().all(|&c| seen[c as usize]));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_alphanumeric() {
+        let s = Pcg::new(42, 54).alphanumeric(32);
+        assert_eq!(s.len(), 32);
+        assert!(s.chars().all(|c| c.is_ascii_alphanumeric()));
+        assert_eq!(s, Pcg::new(42, 54).alphanumeric(32));
+        assert!(Pcg::new(42, 54).alphanumeric(0).is_empty());
+    }
+
+    #[test]
+    fn test_byte_order_round_trip() {
+        let mut a = Pcg::new(42, 54);
+        let mut b = a.clone();
+        for _ in 0..100 {
+            assert_eq!(u64::from_le_bytes(a.next_u64_le()), b.next_u64());
+            assert_eq!(u64::from_be_bytes(a.next_u64_be()), b.next_u64());
+        }
+    }
+
+    #[test]
+    fn test_next_u64_pair() {
+        let mut a = Pcg::new(42, 54);
+        let mut b = a.clone();
+        for _ in 0..100 {
+            let (first, second) = a.next_u64_pair();
+            assert_eq!(first, b.next_u64());
+            assert_eq!(second, b.next_u64());
+        }
+        assert_eq!(a, b);
+    }
+}
@@ -0,0 +1,411 @@
+//! Methods for randomly reordering and choosing from slices without `rand`'s `SliceRandom`.
+
+use crate::{OutputFn, Pcg};
+
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+
+impl<O: OutputFn> Pcg<O> {
+    /// Shuffles a slice in place with an unbiased Fisher-Yates shuffle.
+    ///
+    /// Indices are chosen with `next_bounded_u64`, so every permutation is equally likely. The
+    /// result is reproducible for a given generator state. Empty and single-element slices are
+    /// left untouched and don't consume any randomness.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut rng = Pcg::default();
+    /// let mut cards = [1, 2, 3, 4, 5];
+    /// rng.shuffle(&mut cards);
+    /// ```
+    pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+        for i in (1..slice.len()).rev() {
+            let j = self.next_bounded_u64(i as u64 + 1) as usize;
+            slice.swap(i, j);
+        }
+    }
+
+    /// Returns a reference to a uniformly chosen element of a slice, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut rng = Pcg::default();
+    /// let colors = ["red", "green", "blue"];
+    /// assert!(rng.choose(&colors).is_some());
+    /// assert!(rng.choose::<u32>(&[]).is_none());
+    /// ```
+    pub fn choose<'a, T>(&mut self, slice: &'a [T]) -> Option<&'a T> {
+        if slice.is_empty() {
+            return None;
+        }
+        let i = self.next_bounded_u64(slice.len() as u64) as usize;
+        slice.get(i)
+    }
+
+    /// Returns a mutable reference to a uniformly chosen element of a slice, or `None` if it is
+    /// empty.
+    pub fn choose_mut<'a, T>(&mut self, slice: &'a mut [T]) -> Option<&'a mut T> {
+        if slice.is_empty() {
+            return None;
+        }
+        let i = self.next_bounded_u64(slice.len() as u64) as usize;
+        slice.get_mut(i)
+    }
+
+    /// Returns a reference to an element of `items`, chosen with probability proportional to the
+    /// matching entry of `weights`.
+    ///
+    /// Returns `None` if the slices have different lengths, are empty, or all of the weights are
+    /// zero. The cumulative sums of the weights are collected once, and `next_f64() * total` is
+    /// located among them with a binary search. Elements with a weight of zero are never chosen.
+    /// This requires the `std` feature.
+    ///
+    /// # Panics
+    ///
+    /// This panics if any weight is negative, infinite or NaN, or if the weights are so large
+    /// that their sum overflows to infinity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut rng = Pcg::default();
+    /// let sides = ["heads", "tails", "edge"];
+    /// let side = rng.choose_weighted(&sides, &[0.49, 0.49, 0.02]);
+    /// assert!(side.is_some());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn choose_weighted<'a, T>(&mut self, items: &'a [T], weights: &[f64]) -> Option<&'a T> {
+        if items.len() != weights.len() {
+            return None;
+        }
+
+        let mut cumulative = Vec::with_capacity(weights.len());
+        let mut total = 0.0;
+        for &weight in weights.iter() {
+            assert!(
+                weight >= 0.0 && weight.is_finite(),
+                "weights must be finite and non-negative, got {}",
+                weight
+            );
+            total += weight;
+            cumulative.push(total);
+        }
+        assert!(total.is_finite(), "the sum of the weights must be finite");
+        if total == 0.0 {
+            return None;
+        }
+
+        // The first element whose cumulative sum passes the target. A zero weight repeats the sum
+        // before it, so the element ahead of it is always found first.
+        let target = self.next_f64() * total;
+        let mut index = cumulative.partition_point(|&sum| sum <= target);
+        if index == cumulative.len() {
+            // Rounding can leave the target at the total, so take the last nonzero weight
+            index = cumulative.partition_point(|&sum| sum < total);
+        }
+        items.get(index)
+    }
+
+    /// Draws `k` distinct indices from `0..n`, without replacement, in random order.
+    ///
+    /// When `k` is a large fraction of `n`, this runs a partial Fisher-Yates shuffle over all `n`
+    /// indices. Otherwise it draws indices one at a time and rejects repeats, which avoids
+    /// allocating `n` indices when only a few are needed. Either way, the result is reproducible
+    /// for a given generator state. This requires the `std` feature.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `k` is greater than `n`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut rng = Pcg::default();
+    /// let picks = rng.sample_indices(1000, 10);
+    /// assert_eq!(picks.len(), 10);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn sample_indices(&mut self, n: usize, k: usize) -> Vec<usize> {
+        assert!(k <= n, "cannot sample {} distinct indices from {}", k, n);
+
+        // Rejection only needs a few retries when most indices haven't been picked yet
+        if k <= n / 8 {
+            let mut seen = HashSet::with_capacity(k);
+            let mut indices = Vec::with_capacity(k);
+            while indices.len() < k {
+                let i = self.next_bounded_u64(n as u64) as usize;
+                if seen.insert(i) {
+                    indices.push(i);
+                }
+            }
+            return indices;
+        }
+
+        let mut indices: Vec<usize> = (0..n).collect();
+        for i in 0..k {
+            let j = i + self.next_bounded_u64((n - i) as u64) as usize;
+            indices.swap(i, j);
+        }
+        indices.truncate(k);
+        indices
+    }
+
+    /// Chooses `k` items from an iterator of unknown length in a single pass, with every item
+    /// equally likely to be chosen.
+    ///
+    /// This is Algorithm R: the first `k` items fill the reservoir, and each later item replaces
+    /// a random entry with a probability that shrinks as more items are seen. If the iterator has
+    /// fewer than `k` items, all of them are returned. The order of the returned items is not
+    /// itself random. This requires the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut rng = Pcg::default();
+    /// let sample = rng.reservoir_sample(0..1_000_000, 5);
+    /// assert_eq!(sample.len(), 5);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn reservoir_sample<I: Iterator>(&mut self, iter: I, k: usize) -> Vec<I::Item> {
+        let mut reservoir = Vec::with_capacity(k);
+        if k == 0 {
+            return reservoir;
+        }
+        for (i, item) in iter.enumerate() {
+            if i < k {
+                reservoir.push(item);
+            } else {
+                let j = self.next_bounded_u64(i as u64 + 1) as usize;
+                if j < k {
+                    reservoir[j] = item;
+                }
+            }
+        }
+        reservoir
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shuffle_is_reproducible() {
+        let mut a: Vec<u32> = (0..100).collect();
+        let mut b = a.clone();
+        Pcg::new(42, 54).shuffle(&mut a);
+        Pcg::new(42, 54).shuffle(&mut b);
+        assert_eq!(a, b);
+        assert_ne!(a, (0..100).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn test_shuffle_is_permutation() {
+        let mut values: Vec<u32> = (0..100).collect();
+        Pcg::new(42, 54).shuffle(&mut values);
+        values.sort_unstable();
+        assert_eq!(values, (0..100).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn test_choose_empty() {
+        let mut rng = Pcg::new(42, 54);
+        let mut empty: [u32; 0] = [];
+        assert_eq!(rng.choose(&empty), None);
+        assert_eq!(rng.choose_mut(&mut empty), None);
+    }
+
+    #[test]
+    fn test_choose_is_uniform() {
+        let mut rng = Pcg::new(42, 54);
+        let items = [0usize, 1, 2, 3];
+        let samples = 40_000;
+        let mut counts = [0usize; 4];
+        for _ in 0..samples {
+            counts[*rng.choose(&items).unwrap()] += 1;
+        }
+
+        let expected = samples / items.len();
+        for &count in counts.iter() {
+            assert!((count as f64 - expected as f64).abs() < expected as f64 * 0.05);
+        }
+    }
+
+    #[test]
+    fn test_choose_mut() {
+        let mut rng = Pcg::new(42, 54);
+        let mut items = [0u32; 4];
+        for _ in 0..100 {
+            *rng.choose_mut(&mut items).unwrap() += 1;
+        }
+        assert_eq!(items.iter().sum::<u32>(), 100);
+        assert!(items.iter().all(|&count| count > 0));
+    }
+
+    #[test]
+    fn test_shuffle_trivial_slices() {
+        let mut rng = Pcg::new(42, 54);
+        let mut empty: [u32; 0] = [];
+        rng.shuffle(&mut empty);
+        let mut single = [7];
+        rng.shuffle(&mut single);
+        assert_eq!(single, [7]);
+        assert_eq!(rng, Pcg::new(42, 54));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_sample_indices_distinct_and_in_range() {
+        let mut rng = Pcg::new(42, 54);
+        // These cover both the sparse and the dense strategy
+        for &(n, k) in [(1000, 10), (1000, 500), (100, 13), (1, 1)].iter() {
+            let indices = rng.sample_indices(n, k);
+            assert_eq!(indices.len(), k);
+            assert!(indices.iter().all(|&i| i < n));
+            let unique: HashSet<usize> = indices.iter().copied().collect();
+            assert_eq!(unique.len(), k);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_sample_indices_edge_cases() {
+        let mut rng = Pcg::new(42, 54);
+        assert!(rng.sample_indices(10, 0).is_empty());
+        assert!(rng.sample_indices(0, 0).is_empty());
+
+        let mut all = rng.sample_indices(50, 50);
+        all.sort_unstable();
+        assert_eq!(all, (0..50).collect::<Vec<usize>>());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_sample_indices_is_reproducible() {
+        for &(n, k) in [(1000, 10), (1000, 500)].iter() {
+            let a = Pcg::new(42, 54).sample_indices(n, k);
+            let b = Pcg::new(42, 54).sample_indices(n, k);
+            assert_eq!(a, b);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[should_panic]
+    fn test_sample_indices_too_many() {
+        Pcg::default().sample_indices(3, 4);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_choose_weighted_frequencies() {
+        let mut rng = Pcg::new(42, 54);
+        let items = [0usize, 1, 2, 3];
+        let weights = [1.0, 0.0, 3.0, 6.0];
+        let samples = 100_000;
+        let mut counts = [0usize; 4];
+        for _ in 0..samples {
+            counts[*rng.choose_weighted(&items, &weights).unwrap()] += 1;
+        }
+
+        assert_eq!(counts[1], 0);
+        for (&count, &weight) in counts.iter().zip(weights.iter()) {
+            let rate = count as f64 / samples as f64;
+            assert!(
+                (rate - weight / 10.0).abs() < 0.01,
+                "rate was {} for weight {}",
+                rate,
+                weight
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_choose_weighted_none() {
+        let mut rng = Pcg::new(42, 54);
+        assert_eq!(rng.choose_weighted(&[1, 2], &[1.0]), None);
+        assert_eq!(rng.choose_weighted(&[1, 2], &[0.0, 0.0]), None);
+        assert_eq!(rng.choose_weighted::<u32>(&[], &[]), None);
+        assert_eq!(rng, Pcg::new(42, 54));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[should_panic]
+    fn test_choose_weighted_negative_weight() {
+        Pcg::default().choose_weighted(&[1, 2], &[1.0, -1.0]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[should_panic]
+    fn test_choose_weighted_nan_weight() {
+        Pcg::default().choose_weighted(&[1, 2], &[1.0, f64::NAN]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[should_panic]
+    fn test_choose_weighted_infinite_total() {
+        Pcg::default().choose_weighted(&[1, 2], &[f64::MAX, f64::MAX]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_choose_weighted_skips_zero_weights() {
+        let mut rng = Pcg::new(42, 54);
+        let items = [0, 1, 2, 3, 4];
+        let weights = [0.0, 1.0, 0.0, 0.0, 1.0];
+        for _ in 0..1000 {
+            let item = *rng.choose_weighted(&items, &weights).unwrap();
+            assert!(item == 1 || item == 4, "chose item {}", item);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_reservoir_sample_is_uniform() {
+        let mut rng = Pcg::new(42, 54);
+        let runs = 50_000;
+        let mut counts = [0usize; 5];
+        for _ in 0..runs {
+            for i in rng.reservoir_sample(0..5usize, 2) {
+                counts[i] += 1;
+            }
+        }
+        // Each element should be chosen in 2 out of every 5 runs
+        for (i, &count) in counts.iter().enumerate() {
+            let rate = count as f64 / runs as f64;
+            assert!(
+                (rate - 0.4).abs() < 0.01,
+                "element {} was chosen in {} of runs",
+                i,
+                rate
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_reservoir_sample_edge_cases() {
+        let mut rng = Pcg::new(42, 54);
+        assert!(rng.reservoir_sample(0..10, 0).is_empty());
+        assert_eq!(rng.reservoir_sample(0..3, 5), vec![0, 1, 2]);
+        assert!(rng
+            .reservoir_sample(std::iter::empty::<u32>(), 3)
+            .is_empty());
+    }
+}
@@ -0,0 +1,130 @@
+//! Serialization for `Pcg`, enabled with the `serde` feature.
+//!
+//! In human-readable formats like JSON, a generator is serialized as the same `state:inc` hex
+//! string that the `LowerHex` impl prints, such as `"853c49e6748fea9b:da3e39cb94b95bdb"` for
+//! `Pcg::default()`. Binary formats like bincode keep the compact `(state, inc)` pair. Either way,
+//! only the current position is kept, so a deserialized generator's `reset` rewinds to the state
+//! it was deserialized with.
+
+use crate::{OutputFn, Pcg};
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(feature = "std")]
+use std::{fmt, marker::PhantomData};
+
+#[cfg(not(feature = "std"))]
+use core::{fmt, marker::PhantomData};
+
+/// Serializes a generator as the `LowerHex` string in human-readable formats, so the two can't
+/// drift apart, and as the compact pair otherwise
+impl<O: OutputFn> Serialize for Pcg<O> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&format_args!("{:x}", self))
+        } else {
+            (self.state, self.inc).serialize(serializer)
+        }
+    }
+}
+
+/// Deserializes a generator from either representation. The compact pair has its increment
+/// forced to be odd so that the generator always has a full period, while the hex string is
+/// parsed with `FromStr`, which rejects an even increment.
+impl<'de, O: OutputFn> Deserialize<'de> for Pcg<O> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Pcg<O>, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(HexVisitor(PhantomData))
+        } else {
+            let (state, inc) = <(u64, u64)>::deserialize(deserializer)?;
+            Ok(Pcg::from_parts(state, inc | 1))
+        }
+    }
+}
+
+/// Parses the `state:inc` hex string of a generator
+struct HexVisitor<O>(PhantomData<O>);
+
+impl<'de, O: OutputFn> Visitor<'de> for HexVisitor<O> {
+    type Value = Pcg<O>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a `state:inc` hex string")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Pcg<O>, E> {
+        value
+            .parse::<Pcg>()
+            .map(Pcg::with_output)
+            .map_err(E::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::RxsMXs;
+
+    #[test]
+    fn test_bincode_round_trip() {
+        let mut rng = Pcg::new(42, 54);
+        rng.advance(1000);
+        let bytes = bincode::serialize(&rng).unwrap();
+        assert_eq!(
+            bytes,
+            bincode::serialize(&(rng.state(), rng.increment())).unwrap()
+        );
+
+        let restored: Pcg = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(restored, rng);
+    }
+
+    #[test]
+    fn test_bincode_forces_odd_increment() {
+        let bytes = bincode::serialize(&(42u64, 54u64)).unwrap();
+        let rng: Pcg = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(rng.increment(), 55);
+        assert_eq!(rng, Pcg::from_state(42, 54));
+    }
+
+    #[test]
+    fn test_bincode_layout() {
+        // The state and then the increment, each as a little-endian `u64`, with nothing else
+        let expected = [
+            0x9b, 0xea, 0x8f, 0x74, 0xe6, 0x49, 0x3c, 0x85, // state
+            0xdb, 0x5b, 0xb9, 0x94, 0xcb, 0x39, 0x3e, 0xda, // inc
+        ];
+        assert_eq!(bincode::serialize(&Pcg::default()).unwrap(), expected);
+
+        let restored: Pcg = bincode::deserialize(&expected).unwrap();
+        assert_eq!(restored, Pcg::default());
+        assert_eq!(restored.state(), 0x853c_49e6_748f_ea9b);
+        assert_eq!(restored.increment(), 0xda3e_39cb_94b9_5bdb);
+    }
+
+    #[test]
+    fn test_json_hex_string() {
+        let json = serde_json::to_string(&Pcg::default()).unwrap();
+        assert_eq!(json, "\"853c49e6748fea9b:da3e39cb94b95bdb\"");
+        let restored: Pcg = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, Pcg::default());
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let mut rng = Pcg::new(42, 54).with_output::<RxsMXs>();
+        rng.advance(1000);
+        let json = serde_json::to_string(&rng).unwrap();
+        assert_eq!(json, format!("\"{:x}\"", rng));
+
+        let restored: Pcg<RxsMXs> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, rng);
+    }
+
+    #[test]
+    fn test_json_rejects_invalid() {
+        assert!(serde_json::from_str::<Pcg>("\"853c49e6748fea9b:da3e39cb94b95bda\"").is_err());
+        assert!(serde_json::from_str::<Pcg>("\"853c49e6748fea9b\"").is_err());
+        assert!(serde_json::from_str::<Pcg>("[1, 3]").is_err());
+    }
+}
@@ -0,0 +1,53 @@
+//! Constant-time comparison with `subtle`, enabled with the `subtle` feature.
+//!
+//! The `PartialEq` impl for `Pcg` can return as soon as the states differ, so the time it takes
+//! depends on the values being compared. `ConstantTimeEq` always compares both the state and the
+//! increment in full, which matters when the state was derived from secret material. `PartialEq`
+//! and `Eq` are unchanged.
+
+use crate::Pcg;
+use subtle::{Choice, ConstantTimeEq};
+
+/// Compares the current state and increment, the same fields as `PartialEq`, in constant time
+impl<O> ConstantTimeEq for Pcg<O> {
+    fn ct_eq(&self, other: &Pcg<O>) -> Choice {
+        self.state.ct_eq(&other.state) & self.inc.ct_eq(&other.inc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::RngCore;
+
+    #[test]
+    fn test_ct_eq_equal() {
+        let a = Pcg::new(42, 54);
+        let b = Pcg::new(42, 54);
+        assert!(bool::from(a.ct_eq(&b)));
+        assert!(bool::from(a.ct_eq(&a)));
+
+        // Like `PartialEq`, only the current position matters
+        let mut c = Pcg::new(1, 54);
+        c.advance(5);
+        let d = Pcg::from_state(c.state(), c.increment());
+        assert!(bool::from(c.ct_eq(&d)));
+    }
+
+    #[test]
+    fn test_ct_eq_unequal() {
+        let a = Pcg::new(42, 54);
+
+        let mut stepped = a.clone();
+        stepped.next_u32();
+        assert!(!bool::from(a.ct_eq(&stepped)));
+
+        // Same state, different increment
+        let other_stream = Pcg::from_state(a.state(), a.increment() + 2);
+        assert!(!bool::from(a.ct_eq(&other_stream)));
+
+        // Same increment, different state
+        let other_state = Pcg::from_state(a.state() ^ 1, a.increment());
+        assert!(!bool::from(a.ct_eq(&other_state)));
+    }
+}
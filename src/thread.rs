@@ -0,0 +1,101 @@
+//! A lazily seeded generator for each thread.
+
+use crate::Pcg;
+use std::cell::Cell;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::ops::{Deref, DerefMut};
+
+thread_local! {
+    /// The current thread's generator, which is taken out while a `ThreadPcg` is alive
+    static THREAD_PCG: Cell<Option<Pcg>> = const { Cell::new(None) };
+}
+
+/// Gives access to the current thread's generator, and puts it back when dropped
+struct ThreadPcg {
+    rng: Option<Pcg>,
+}
+
+impl Deref for ThreadPcg {
+    type Target = Pcg;
+
+    fn deref(&self) -> &Pcg {
+        self.rng
+            .as_ref()
+            .expect("the generator is only taken on drop")
+    }
+}
+
+impl DerefMut for ThreadPcg {
+    fn deref_mut(&mut self) -> &mut Pcg {
+        self.rng
+            .as_mut()
+            .expect("the generator is only taken on drop")
+    }
+}
+
+impl Drop for ThreadPcg {
+    fn drop(&mut self) {
+        let rng = self.rng.take();
+        // The thread local may already be gone if this is dropped during thread teardown
+        let _ = THREAD_PCG.try_with(|cell| cell.set(rng));
+    }
+}
+
+/// Returns a randomly seeded generator that belongs to the current thread.
+///
+/// The generator is created the first time this is called on a thread, with a seed and stream
+/// drawn from the randomly keyed hasher behind `std`'s `HashMap`, so it's different on every
+/// thread and in every run and can't be reproduced. Later calls on the same thread continue the
+/// same generator. This is meant for scratch randomness where passing a generator around would
+/// be a hassle; use a seeded `Pcg` when results need to be reproducible. This requires the `std`
+/// feature.
+///
+/// The generator is borrowed until the returned value is dropped. Calling this again while an
+/// earlier borrow is still alive gives the inner borrow a separately seeded generator.
+///
+/// # Examples
+///
+/// ```
+/// use rand_core::RngCore;
+///
+/// let value = pcg::thread_pcg().next_u64();
+/// ```
+pub fn thread_pcg() -> impl DerefMut<Target = Pcg> {
+    let rng = THREAD_PCG
+        .with(|cell| cell.take())
+        .unwrap_or_else(seeded_from_random_state);
+    ThreadPcg { rng: Some(rng) }
+}
+
+/// Creates a generator seeded from `std`'s randomly keyed hasher
+fn seeded_from_random_state() -> Pcg {
+    let state = RandomState::new();
+    let mut seed = state.build_hasher();
+    seed.write_u8(0);
+    let mut seq = state.build_hasher();
+    seq.write_u8(1);
+    Pcg::new(seed.finish(), seq.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::RngCore;
+
+    #[test]
+    fn test_thread_pcg_continues_between_calls() {
+        let mut expected = thread_pcg().clone();
+        for _ in 0..10 {
+            assert_eq!(thread_pcg().next_u64(), expected.next_u64());
+        }
+        assert_eq!(*thread_pcg(), expected);
+    }
+
+    #[test]
+    fn test_thread_pcg_differs_between_threads() {
+        let here = thread_pcg().clone();
+        let there = std::thread::spawn(|| thread_pcg().clone()).join().unwrap();
+        assert_ne!(here, there);
+    }
+}
@@ -0,0 +1,87 @@
+//! Constructors that automatically put each new generator on a stream of its own.
+
+use crate::mix::mix64;
+use crate::Pcg;
+
+#[cfg(feature = "std")]
+use std::sync::atomic::{AtomicU64, Ordering};
+
+#[cfg(not(feature = "std"))]
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// Counts the calls to `new_unique`, so that generators created at the same address still differ
+static UNIQUE_COUNT: AtomicU64 = AtomicU64::new(0);
+
+/// The stream that the next call to `next_stream` will use
+static NEXT_STREAM: AtomicU64 = AtomicU64::new(0);
+
+impl Pcg {
+    /// Constructs a new generator from a seed, on a stream derived from a memory address.
+    ///
+    /// Like the reference library's "unique" generators, each generator gets a distinct stream
+    /// without the caller having to manage stream IDs. The stream is hashed from the address of
+    /// a local variable together with a count of previous calls, so two generators are on
+    /// different streams even if they're created at the same address. Since addresses change
+    /// from run to run, the stream, and therefore the output, can't be reproduced. Use `new` or
+    /// `next_stream` when it needs to be.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let a = Pcg::new_unique(42);
+    /// let b = Pcg::new_unique(42);
+    /// assert_ne!(a.stream(), b.stream());
+    /// ```
+    pub fn new_unique(seed: u64) -> Pcg {
+        let marker = 0u8;
+        let address = &marker as *const u8 as usize as u64;
+        let count = UNIQUE_COUNT.fetch_add(1, Ordering::Relaxed);
+        Pcg::new(seed, mix64(address ^ mix64(count)))
+    }
+
+    /// Constructs a new generator from a seed, on the next stream of a global counter.
+    ///
+    /// The first call in a process uses stream 0, the next uses stream 1, and so on, so every
+    /// generator created this way is on a different stream. The counter is atomic, so this can
+    /// be called from any thread, but the streams are only reproducible from run to run if the
+    /// calls happen in the same order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let a = Pcg::next_stream(42);
+    /// let b = Pcg::next_stream(42);
+    /// assert!(b.stream() > a.stream());
+    /// ```
+    pub fn next_stream(seed: u64) -> Pcg {
+        Pcg::new(seed, NEXT_STREAM.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::RngCore;
+
+    #[test]
+    fn test_next_stream_increases() {
+        // Other tests may take streams in between, so they're only guaranteed to increase
+        let streams: Vec<u64> = (0..10).map(|_| Pcg::next_stream(42).stream()).collect();
+        assert!(streams.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let a = Pcg::next_stream(42);
+        assert_eq!(a, Pcg::new(42, a.stream()));
+    }
+
+    #[test]
+    fn test_new_unique_streams_differ() {
+        let mut a = Pcg::new_unique(42);
+        let mut b = Pcg::new_unique(42);
+        assert_ne!(a.stream(), b.stream());
+        assert_ne!(a.next_u64(), b.next_u64());
+    }
+}
@@ -0,0 +1,77 @@
+//! Random UUID generation, enabled with the `uuid` feature.
+
+use crate::{OutputFn, Pcg};
+use rand_core::RngCore;
+use uuid::{Builder, Uuid};
+
+impl<O: OutputFn> Pcg<O> {
+    /// Generates a random version 4 UUID.
+    ///
+    /// The 16 bytes come from `fill_bytes`, and then the version and variant bits are set as
+    /// RFC 4122 requires, which leaves 122 random bits. A seeded generator always produces the
+    /// same UUIDs, which is handy for reproducible tests, but these UUIDs are no harder to guess
+    /// than the generator's state, since PCG is not cryptographically secure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pcg::Pcg;
+    ///
+    /// let mut rng = Pcg::new(42, 54);
+    /// let id = rng.next_uuid_v4();
+    /// assert_eq!(id.get_version_num(), 4);
+    /// ```
+    pub fn next_uuid_v4(&mut self) -> Uuid {
+        let mut bytes = [0u8; 16];
+        self.fill_bytes(&mut bytes);
+        Builder::from_random_bytes(bytes).into_uuid()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::{Variant, Version};
+
+    #[test]
+    fn test_version_and_variant() {
+        let mut rng = Pcg::new(42, 54);
+        for _ in 0..100 {
+            let id = rng.next_uuid_v4();
+            let bytes = id.as_bytes();
+            assert_eq!(bytes[6] >> 4, 4);
+            assert_eq!(bytes[8] >> 6, 0b10);
+            assert_eq!(id.get_version(), Some(Version::Random));
+            assert_eq!(id.get_variant(), Variant::RFC4122);
+        }
+    }
+
+    #[test]
+    fn test_fixed_seed() {
+        // The bytes are the reference `pcg32` outputs for `(42, 54)`, 0xa15c02b7, 0x7b47f409,
+        // 0xba1d3330 and 0x83d2f293, laid out as `fill_bytes` does, with the version nibble of
+        // 0x5c set to 4. The variant bits of 0x93 are already `10`.
+        let mut rng = Pcg::new(42, 54);
+        assert_eq!(
+            rng.next_uuid_v4().to_string(),
+            "09f4477b-b702-4ca1-93f2-d28330331dba"
+        );
+        assert_eq!(
+            Pcg::new(42, 54).next_uuid_v4(),
+            Pcg::new(42, 54).next_uuid_v4()
+        );
+    }
+
+    #[test]
+    fn test_uses_fill_bytes() {
+        let mut a = Pcg::new(42, 54);
+        let mut b = a.clone();
+        let id = a.next_uuid_v4();
+        let mut bytes = [0u8; 16];
+        b.fill_bytes(&mut bytes);
+        bytes[6] = (bytes[6] & 0x0f) | 0x40;
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
+        assert_eq!(*id.as_bytes(), bytes);
+        assert_eq!(a, b);
+    }
+}
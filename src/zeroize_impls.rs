@@ -0,0 +1,103 @@
+//! Wiping generator state with `zeroize`, enabled with the `zeroize` feature.
+//!
+//! `Pcg` and `PcgSeed` implement `Zeroize`, which overwrites their contents with zeros in a way
+//! the compiler won't optimize away. With the `zeroize-on-drop` feature, `Pcg` is also wiped
+//! whenever it is dropped, and implements the `ZeroizeOnDrop` marker trait.
+//!
+//! This only keeps the state from lingering in memory. PCG is not a cryptographically secure
+//! generator, and its outputs reveal its state, so it shouldn't be used to derive secrets
+//! regardless.
+
+use crate::{Pcg, PcgSeed};
+use zeroize::Zeroize;
+
+#[cfg(feature = "zeroize-on-drop")]
+use zeroize::ZeroizeOnDrop;
+
+/// Overwrites the current and starting state and increment with zeros, along with any value
+/// cached by `next_normal`
+///
+/// A wiped generator has an even increment of 0, so it no longer has a full period. It should be
+/// dropped or reseeded rather than used.
+impl<O> Zeroize for Pcg<O> {
+    fn zeroize(&mut self) {
+        self.state.zeroize();
+        self.inc.zeroize();
+        self.init_state.zeroize();
+        self.init_inc.zeroize();
+        #[cfg(feature = "std")]
+        self.cached_normal.zeroize();
+        #[cfg(feature = "trace")]
+        self.count.zeroize();
+    }
+}
+
+/// Overwrites the seed bytes with zeros
+impl<const N: usize> Zeroize for PcgSeed<N> {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize-on-drop")]
+impl<O> Drop for Pcg<O> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize-on-drop")]
+impl<O> ZeroizeOnDrop for Pcg<O> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::RxsMXs;
+    use rand_core::RngCore;
+
+    #[test]
+    fn test_zeroize_pcg() {
+        let mut rng = Pcg::new(42, 54);
+        rng.next_u32();
+        rng.zeroize();
+        assert_eq!(rng.state(), 0);
+        assert_eq!(rng.increment(), 0);
+
+        // The starting point that `reset` rewinds to is wiped too
+        rng.reset();
+        assert_eq!(rng.state(), 0);
+        assert_eq!(rng.increment(), 0);
+
+        let mut rng = Pcg::new(42, 54).with_output::<RxsMXs>();
+        rng.zeroize();
+        assert_eq!((rng.state(), rng.increment()), (0, 0));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_zeroize_discards_cached_normal() {
+        let mut rng = Pcg::new(42, 54);
+        rng.next_normal(0.0, 1.0);
+        rng.zeroize();
+        assert_eq!(rng.cached_normal, None);
+    }
+
+    #[test]
+    fn test_zeroize_seed() {
+        let mut seed = PcgSeed([1, 2, 3, 4, 5, 6, 7, 8]);
+        seed.zeroize();
+        assert_eq!(seed, PcgSeed([0; 8]));
+
+        let mut seed = crate::Pcg64Seed::from(u128::MAX);
+        seed.zeroize();
+        assert_eq!(seed.0, [0; 16]);
+    }
+
+    #[cfg(feature = "zeroize-on-drop")]
+    #[test]
+    fn test_zeroize_on_drop() {
+        fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+        assert_zeroize_on_drop::<Pcg>();
+        assert_zeroize_on_drop::<Pcg<RxsMXs>>();
+    }
+}